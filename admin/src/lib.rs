@@ -1,30 +1,44 @@
 #![no_std]
 
-use soroban_sdk::{Address, Env, contract, contracttype};
+use soroban_sdk::{Address, Env, Symbol, Vec, contract, contracttype, vec};
 
 use admin_sep::{
-    Admin, Administratable, AdministratableExt, Constructable, HasAdmin, Upgradable, Upgrader,
-    derive_contract,
+    AccessControl, AccessControlExt, Admin, AdminError, Administratable, AdministratableExt,
+    Construct, Constructable, Constructor, Extendable, Extender, HasAdmin, HasRoles, Pausable,
+    PausableExt, Pauser, Recoverable, Recovery, Roles, Upgradable, Upgrader, derive_contract,
 };
 
 #[contract]
-#[derive_contract(Administratable, Upgradable(ext = AdministratableExt))]
+#[derive_contract(
+    Administratable,
+    Upgradable(ext = AdministratableExt),
+    Extendable(ext = AdministratableExt),
+    AccessControl(ext = AdministratableExt, ext = PausableExt),
+    Pausable(ext = AdministratableExt),
+    Recoverable(ext = AdministratableExt),
+    Constructable(
+        ext = AdministratableExt,
+        ext = AccessControlExt,
+        args = CustomArgs,
+        default = Constructor<Contract>
+    )
+)]
 pub struct Contract;
 
-Constructable!(Contract, Contract, CustomArgs);
-
 #[contracttype]
-pub struct CustomArgs(pub Address, pub u32);
+#[derive(HasAdmin)]
+pub struct CustomArgs(#[admin] pub Address, pub u32);
 
-impl HasAdmin for CustomArgs {
-    fn admin(&self) -> &soroban_sdk::Address {
-        &self.0
+/// No roles are granted up front, the admin grants them later.
+impl HasRoles for CustomArgs {
+    fn roles(&self, env: &Env) -> Vec<(Symbol, Address)> {
+        vec![env]
     }
 }
 
 const COUNT: soroban_sdk::Symbol = soroban_sdk::symbol_short!("COUNT");
 
-impl Constructable<CustomArgs> for Contract {
+impl Construct<CustomArgs> for Contract {
     fn construct(env: &Env, args: CustomArgs) {
         env.storage().persistent().set(&COUNT, &args.1);
     }
@@ -32,11 +46,12 @@ impl Constructable<CustomArgs> for Contract {
 
 #[soroban_sdk::contractimpl]
 impl Contract {
-    pub fn increment(env: Env) -> u32 {
+    pub fn increment(env: Env) -> Result<u32, AdminError> {
+        Self::require_not_paused(&env)?;
         let mut count: u32 = env.storage().persistent().get(&COUNT).unwrap_or(0);
         count += 1;
         env.storage().persistent().set(&COUNT, &count);
-        count
+        Ok(count)
     }
 }
 
@@ -1,7 +1,38 @@
 #![cfg(test)]
 extern crate std;
 use crate::{Contract, ContractClient, CustomArgs};
-use soroban_sdk::{Address, Env, testutils::Address as _};
+use admin_sep::{
+    AdminError, DAY_IN_LEDGERS, RecoveryConfig, TtlConfig, Upgradable, UpgradeRecord, events,
+};
+use soroban_sdk::{
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+    auth::{Context, ContractContext},
+    symbol_short,
+    testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger,
+        storage::{Instance as _, Persistent as _},
+    },
+    vec,
+    xdr::{
+        Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization, InvokeContractArgs, Limits,
+        ScBytes, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry,
+        SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials, VecM, WriteXdr,
+    },
+};
+
+/// Upload the smallest wasm the host accepts as a contract, to have something to upgrade to.
+/// Once upgraded, the contract's native code can still be called with `env.as_contract`.
+fn upload_wasm(env: &Env) -> BytesN<32> {
+    let wasm: &[u8] = b"\0asm\x01\0\0\0\x00\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x16\0\0\0\0";
+    env.deployer().upload_contract_wasm(wasm)
+}
+
+/// The same wasm as `upload_wasm` with an extra custom section, for a second hash.
+fn upload_other_wasm(env: &Env) -> BytesN<32> {
+    let wasm: &[u8] =
+        b"\0asm\x01\0\0\0\x00\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x16\0\0\0\0\x00\x02\x01x";
+    env.deployer().upload_contract_wasm(wasm)
+}
 
 #[test]
 fn test() {
@@ -15,3 +46,1342 @@ fn test() {
     assert_eq!(client.increment(), 3);
     assert_eq!(client.increment(), 4);
 }
+
+#[test]
+fn admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+
+    client.propose_admin(&new_admin, &None);
+    assert_eq!(client.pending_admin().unwrap().address, new_admin);
+    assert_eq!(client.admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(client.admin(), new_admin);
+    assert_eq!(client.pending_admin(), None);
+
+    client.propose_admin(&admin, &None);
+    client.cancel_admin_transfer();
+    assert_eq!(client.pending_admin(), None);
+    assert_eq!(
+        client.try_accept_admin(),
+        Err(Ok(AdminError::NoPendingAdmin))
+    );
+}
+
+#[test]
+fn legacy_admin_key() {
+    use admin_sep::{AdministratableKey, LEGACY_STORAGE_KEY};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let old_admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    let admin_key = (Symbol::new(&env, "admin_sep"), AdministratableKey::Admin);
+
+    // Storage left behind by a version that kept the admin under the bare "A" key.
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.remove(&admin_key);
+        storage.set(&LEGACY_STORAGE_KEY, &old_admin);
+    });
+    assert_eq!(client.admin(), old_admin);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        assert!(!storage.has(&LEGACY_STORAGE_KEY));
+        assert_eq!(
+            storage.get::<_, Address>(&admin_key),
+            Some(old_admin.clone())
+        );
+    });
+    client.set_admin(&admin);
+    assert_eq!(client.admin(), admin);
+}
+
+#[test]
+fn admin_transfer_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+
+    let live_until_ledger = env.ledger().sequence() + 10;
+    client.propose_admin(&Address::generate(&env), &Some(live_until_ledger));
+    env.ledger().set_sequence_number(live_until_ledger + 1);
+    assert_eq!(
+        client.try_accept_admin(),
+        Err(Ok(AdminError::PendingAdminExpired))
+    );
+}
+
+#[test]
+fn admin_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+
+    client.set_admin(&new_admin);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id,
+                (Symbol::new(&env, events::ADMIN_SET),).into_val(&env),
+                (admin, new_admin).into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn extend_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    let instance_ttl = || env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+
+    assert_eq!(client.ttl_config(), TtlConfig::DEFAULT);
+    client.extend_ttl();
+    assert_eq!(instance_ttl(), TtlConfig::DEFAULT.extend_to);
+
+    let config = TtlConfig {
+        threshold: 60 * DAY_IN_LEDGERS,
+        extend_to: 90 * DAY_IN_LEDGERS,
+        anyone_can_extend: false,
+    };
+    client.set_ttl_config(&config);
+    client.extend_ttl();
+    assert_eq!(instance_ttl(), config.extend_to);
+
+    assert_eq!(
+        client.try_set_ttl_config(&TtlConfig {
+            threshold: 2,
+            extend_to: 1,
+            anyone_can_extend: true,
+        }),
+        Err(Ok(AdminError::InvalidTtlConfig))
+    );
+}
+
+#[test]
+fn roles() {
+    use admin_sep::AccessControlKey;
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let minter_admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    let minter_role = admin_sep::MINTER_ROLE;
+    let minter_admin_role = symbol_short!("mint_adm");
+
+    assert_eq!(
+        client.try_grant_role(&minter_admin, &minter_role, &minter),
+        Err(Ok(AdminError::Unauthorized))
+    );
+
+    client.grant_role(&admin, &minter_admin_role, &minter_admin);
+    client.set_role_admin(&minter_role, &Some(minter_admin_role.clone()));
+    assert_eq!(client.role_admin(&minter_role), Some(minter_admin_role));
+
+    client.grant_role(&minter_admin, &minter_role, &minter);
+    assert!(client.has_role(&minter_role, &minter));
+
+    env.as_contract(&contract_id, || {
+        let key = (
+            Symbol::new(&env, "admin_sep"),
+            AccessControlKey::Role(minter_role.clone(), minter.clone()),
+        );
+        assert!(!env.storage().instance().has(&key));
+        assert!(env.storage().persistent().has(&key));
+    });
+
+    client.renounce_role(&minter_role, &minter);
+    assert!(!client.has_role(&minter_role, &minter));
+    assert_eq!(
+        client.try_revoke_role(&minter_admin, &minter_role, &minter),
+        Err(Ok(AdminError::RoleNotHeld))
+    );
+}
+
+mod multisig {
+    use admin_sep::{
+        Administratable, AdministratableExt, Constructable, Constructor, Multisig, MultisigAdmin,
+        derive_contract,
+    };
+    use soroban_sdk::{Env, contract};
+
+    #[contract]
+    #[derive_contract(
+        Administratable(default = MultisigAdmin<MultisigContract>),
+        Multisig,
+        Constructable(ext = AdministratableExt)
+    )]
+    pub struct MultisigContract;
+}
+
+#[test]
+fn multisig() {
+    use multisig::{MultisigContract, MultisigContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let [first, second, third] = [(); 3].map(|_| Address::generate(&env));
+    let contract_id = env.register(MultisigContract, (first.clone(),));
+    let client = MultisigContractClient::new(&env, &contract_id);
+    assert_eq!(client.admin(), contract_id);
+
+    client.set_signer(&second, &1);
+    client.set_signer(&third, &2);
+    client.set_threshold(&3);
+    assert_eq!(client.threshold(), 3);
+    assert_eq!(
+        client.try_set_threshold(&5),
+        Err(Ok(AdminError::InvalidThreshold))
+    );
+
+    let payload = BytesN::from_array(&env, &[0; 32]);
+    let call = |contract: &Address| {
+        Context::Contract(ContractContext {
+            contract: contract.clone(),
+            fn_name: symbol_short!("set_admin"),
+            args: vec![&env],
+        })
+    };
+    let check_auth_for = |signatures: Vec<Address>, contexts: Vec<Context>| {
+        env.try_invoke_contract_check_auth::<AdminError>(
+            &contract_id,
+            &payload,
+            signatures.into_val(&env),
+            &contexts,
+        )
+    };
+    let check_auth = |signatures| check_auth_for(signatures, vec![&env, call(&contract_id)]);
+    assert_eq!(
+        check_auth(vec![&env, first.clone(), second.clone()]),
+        Err(Ok(AdminError::Unauthorized))
+    );
+    assert_eq!(
+        check_auth(vec![&env, second.clone(), second.clone(), first.clone()]),
+        Err(Ok(AdminError::Unauthorized))
+    );
+    assert_eq!(check_auth(vec![&env, first.clone(), third.clone()]), Ok(()));
+    // Signers only approve calls to the multisig's own functions.
+    assert_eq!(
+        check_auth_for(
+            vec![&env, first.clone(), third],
+            vec![&env, call(&contract_id), call(&Address::generate(&env))]
+        ),
+        Err(Ok(AdminError::Unauthorized))
+    );
+
+    client.set_admin(&first);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, events::ADMIN_SET),).into_val(&env),
+                (contract_id, first).into_val(&env),
+            ),
+        ]
+    );
+}
+
+mod wallet {
+    use admin_sep::AdminError;
+    use soroban_sdk::{BytesN, Env, Vec, auth::Context, contract, contractimpl};
+
+    /// An account approving everything, to sign for a multisig without keys.
+    #[contract]
+    pub struct Wallet;
+
+    #[contractimpl]
+    impl Wallet {
+        pub fn __check_auth(
+            _env: Env,
+            _signature_payload: BytesN<32>,
+            _signature: (),
+            _auth_contexts: Vec<Context>,
+        ) -> Result<(), AdminError> {
+            Ok(())
+        }
+    }
+}
+
+/// The entries for `signers` to authorize calling `fn_name` with `args` on the multisig
+/// `contract_id`, through its `__check_auth`.
+fn multisig_auths(
+    env: &Env,
+    contract_id: &Address,
+    nonce: i64,
+    fn_name: &str,
+    args: Vec<Val>,
+    signers: Vec<Address>,
+) -> std::vec::Vec<SorobanAuthorizationEntry> {
+    let to_sc_val = |val: Val| ScVal::try_from_val(env, &val).unwrap();
+    let invocation = |fn_name: &str, args: std::vec::Vec<ScVal>| SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address: contract_id.into(),
+            function_name: fn_name.try_into().unwrap(),
+            args: args.try_into().unwrap(),
+        }),
+        sub_invocations: VecM::default(),
+    };
+    let signature_expiration_ledger = env.ledger().sequence() + 100;
+    let entry = |address: &Address, signature, root_invocation| SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: address.into(),
+            nonce,
+            signature_expiration_ledger,
+            signature,
+        }),
+        root_invocation,
+    };
+
+    let root_invocation = invocation(fn_name, args.iter().map(to_sc_val).collect());
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: Hash(env.ledger().network_id().to_array()),
+        nonce,
+        signature_expiration_ledger,
+        invocation: root_invocation.clone(),
+    });
+    let preimage = Bytes::from_slice(env, &preimage.to_xdr(Limits::none()).unwrap());
+    let payload = env.crypto().sha256(&preimage).to_array();
+    let mut auths = std::vec![entry(
+        contract_id,
+        to_sc_val(signers.to_val()),
+        root_invocation,
+    )];
+    for signer in signers.iter() {
+        let check_auth = invocation(
+            "__check_auth",
+            std::vec![ScVal::Bytes(ScBytes(payload.try_into().unwrap()))],
+        );
+        auths.push(entry(&signer, ScVal::Void, check_auth));
+    }
+    auths
+}
+
+#[test]
+fn multisig_signer_auth() {
+    use multisig::{MultisigContract, MultisigContractClient};
+    let env = Env::default();
+    let [first, second] = [(); 2].map(|_| env.register(wallet::Wallet, ()));
+    let contract_id = env.register(MultisigContract, (first.clone(),));
+    let client = MultisigContractClient::new(&env, &contract_id);
+    let authorize = |nonce, fn_name, args, signers| {
+        env.set_auths(&multisig_auths(
+            &env,
+            &contract_id,
+            nonce,
+            fn_name,
+            args,
+            signers,
+        ));
+    };
+
+    authorize(
+        1,
+        "set_signer",
+        vec![&env, second.into_val(&env), 1u32.into_val(&env)],
+        vec![&env, first.clone()],
+    );
+    client.set_signer(&second, &1);
+    let threshold_args = |threshold: u32| vec![&env, threshold.into_val(&env)];
+    authorize(
+        2,
+        "set_threshold",
+        threshold_args(2),
+        vec![&env, first.clone()],
+    );
+    client.set_threshold(&2);
+    assert_eq!(client.threshold(), 2);
+
+    // `first` alone no longer reaches the threshold.
+    authorize(
+        3,
+        "set_threshold",
+        threshold_args(1),
+        vec![&env, first.clone()],
+    );
+    assert!(client.try_set_threshold(&1).is_err());
+    authorize(
+        4,
+        "set_threshold",
+        threshold_args(1),
+        vec![&env, first, second],
+    );
+    client.set_threshold(&1);
+    assert_eq!(client.threshold(), 1);
+}
+
+mod timelock {
+    use admin_sep::{
+        Admin, Administratable, AdministratableExt, Constructable, Constructor, Timelock,
+        TimelockController, TimelockExt, Upgradable, Upgrader, derive_contract,
+    };
+    use soroban_sdk::{Env, contract};
+
+    #[contract]
+    #[derive_contract(
+        Administratable,
+        Timelock(ext = AdministratableExt),
+        Upgradable(ext = AdministratableExt, ext = TimelockExt),
+        Constructable(ext = AdministratableExt)
+    )]
+    pub struct TimelockContract;
+}
+
+#[test]
+fn timelock() {
+    use timelock::{TimelockContract, TimelockContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(TimelockContract, (admin.clone(),));
+    let client = TimelockContractClient::new(&env, &contract_id);
+    client.set_min_delay(&100);
+
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    let fn_name = symbol_short!("upgrade");
+    let args = vec![&env, wasm_hash.into_val(&env)];
+    assert_eq!(
+        client.try_upgrade(&wasm_hash, &admin),
+        Err(Ok(AdminError::OperationNotScheduled))
+    );
+    assert_eq!(
+        client.try_schedule(&fn_name, &args, &99),
+        Err(Ok(AdminError::DelayTooShort))
+    );
+
+    let id = client.schedule(&fn_name, &args, &100);
+    let ready_ledger = env.ledger().sequence() + 100;
+    assert_eq!(
+        client.get_operation(&id).unwrap().ready_ledger,
+        ready_ledger
+    );
+    assert_eq!(
+        client.try_upgrade(&wasm_hash, &admin),
+        Err(Ok(AdminError::OperationNotReady))
+    );
+
+    client.cancel(&id);
+    assert_eq!(client.get_operation(&id), None);
+    assert_eq!(
+        client.try_set_min_delay(&0),
+        Err(Ok(AdminError::OperationNotScheduled))
+    );
+}
+
+#[test]
+fn timelock_upgrade() {
+    use timelock::{TimelockContract, TimelockContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(TimelockContract, (admin.clone(),));
+    let client = TimelockContractClient::new(&env, &contract_id);
+    client.set_min_delay(&100);
+
+    let wasm_hash = upload_wasm(&env);
+    let args = vec![&env, wasm_hash.into_val(&env)];
+    let id = client.schedule(&symbol_short!("upgrade"), &args, &100);
+    let key = admin_sep::storage_key::<admin_sep::AdminSep, _>(
+        &env,
+        admin_sep::TimelockKey::Operation(id.clone()),
+    );
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 100);
+    client.upgrade(&wasm_hash, &admin);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            <timelock::TimelockContract as Upgradable>::current_wasm_hash(&env),
+            Some(wasm_hash.clone())
+        );
+        assert!(!env.storage().persistent().has(&key));
+    });
+}
+
+#[test]
+fn pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_pause(&Address::generate(&env)),
+        Err(Ok(AdminError::Unauthorized))
+    );
+    client.pause(&admin);
+    assert!(client.paused());
+    assert_eq!(client.try_increment(), Err(Ok(AdminError::Paused)));
+    assert_eq!(client.try_pause(&admin), Err(Ok(AdminError::Paused)));
+
+    let minter = Address::generate(&env);
+    assert_eq!(
+        client.try_grant_role(&admin, &admin_sep::MINTER_ROLE, &minter),
+        Err(Ok(AdminError::Paused))
+    );
+    assert_eq!(
+        client.try_revoke_role(&admin, &admin_sep::MINTER_ROLE, &minter),
+        Err(Ok(AdminError::RoleNotHeld))
+    );
+
+    client.unpause(&admin);
+    client.grant_role(&admin, &admin_sep::MINTER_ROLE, &minter);
+    assert_eq!(client.increment(), 2);
+    assert_eq!(client.try_unpause(&admin), Err(Ok(AdminError::NotPaused)));
+}
+
+mod pauser {
+    use admin_sep::{
+        AccessControl, AccessControlExt, Admin, Administratable, AdministratableExt, Constructable,
+        Constructor, HasAdmin, HasRoles, PAUSER_ROLE, Pausable, Pauser, Roles, derive_contract,
+    };
+    use soroban_sdk::{Address, Env, Symbol, Vec, contract, contracttype, vec};
+
+    #[contract]
+    #[derive_contract(
+        Administratable,
+        AccessControl(ext = AdministratableExt),
+        Pausable(ext = AccessControlExt),
+        Constructable(ext = AdministratableExt, ext = AccessControlExt, args = PauserArgs)
+    )]
+    pub struct PauserContract;
+
+    #[contracttype]
+    #[derive(HasAdmin)]
+    pub struct PauserArgs {
+        #[admin]
+        pub admin: Address,
+        pub pauser: Address,
+    }
+
+    impl HasRoles for PauserArgs {
+        fn roles(&self, env: &Env) -> Vec<(Symbol, Address)> {
+            vec![env, (PAUSER_ROLE, self.pauser.clone())]
+        }
+    }
+}
+
+#[test]
+fn pause_role() {
+    use pauser::{PauserArgs, PauserContract, PauserContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    let args = PauserArgs {
+        admin: admin.clone(),
+        pauser: pauser.clone(),
+    };
+    let contract_id = env.register(PauserContract, (args,));
+    let client = PauserContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.try_pause(&admin), Err(Ok(AdminError::Unauthorized)));
+    client.pause(&pauser);
+    assert!(client.paused());
+    client.unpause(&pauser);
+    assert!(!client.paused());
+
+    client.revoke_role(&admin, &admin_sep::PAUSER_ROLE, &pauser);
+    assert_eq!(client.try_pause(&pauser), Err(Ok(AdminError::Unauthorized)));
+}
+
+#[test]
+fn admin_auth_bound_to_args() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let account = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    let role = symbol_short!("minter");
+
+    client.grant_role(&admin, &role, &account);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "grant_role"),
+                    vec![&env, role.into_val(&env), account.into_val(&env)],
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+
+    let admin_role = Some(symbol_short!("owner"));
+    client.set_role_admin(&role, &admin_role);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin,
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id,
+                    Symbol::new(&env, "set_role_admin"),
+                    vec![&env, role.into_val(&env), admin_role.into_val(&env)],
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+}
+
+#[test]
+fn upgrade_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    let wasm_hash = upload_wasm(&env);
+    assert_eq!(client.current_wasm_hash(), None);
+
+    client.upgrade(&wasm_hash, &admin);
+    let first = UpgradeRecord {
+        old_wasm_hash: None,
+        new_wasm_hash: wasm_hash.clone(),
+        ledger: env.ledger().sequence(),
+        operator: admin.clone(),
+    };
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            <Contract as Upgradable>::current_wasm_hash(&env),
+            Some(wasm_hash.clone())
+        );
+        <Contract as Upgradable>::upgrade(&env, wasm_hash.clone(), &admin).unwrap();
+        let second = UpgradeRecord {
+            old_wasm_hash: Some(wasm_hash.clone().into()),
+            ledger: env.ledger().sequence(),
+            ..first.clone()
+        };
+        let history = |start, limit| <Contract as Upgradable>::upgrade_history(&env, start, limit);
+        assert_eq!(history(0, 10), vec![&env, first.clone(), second.clone()]);
+        assert_eq!(history(0, 1), vec![&env, first]);
+        assert_eq!(history(1, 10), vec![&env, second]);
+        assert_eq!(history(2, 10), vec![&env]);
+    });
+}
+
+#[test]
+fn recovery() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let recovery = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_initiate_recovery(),
+        Err(Ok(AdminError::NoRecovery))
+    );
+    let config = RecoveryConfig {
+        address: recovery.clone(),
+        inactivity_ledgers: 100,
+    };
+    client.set_recovery(&Some(config.clone()));
+    assert_eq!(client.recovery(), Some(config));
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 99);
+    assert_eq!(
+        client.try_initiate_recovery(),
+        Err(Ok(AdminError::AdminActive))
+    );
+    client.heartbeat();
+    assert_eq!(client.last_heartbeat(), env.ledger().sequence());
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 100);
+    client.initiate_recovery();
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id,
+                (Symbol::new(&env, events::RECOVERY_INITIATED),).into_val(&env),
+                recovery.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.pending_admin().unwrap().address, recovery);
+    client.accept_admin();
+    assert_eq!(client.admin(), recovery);
+}
+
+#[test]
+fn recovery_heartbeat_on_admin_calls() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    client.set_recovery(&Some(RecoveryConfig {
+        address: Address::generate(&env),
+        inactivity_ledgers: 100,
+    }));
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 99);
+    client.pause(&admin);
+    assert_eq!(client.last_heartbeat(), env.ledger().sequence());
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 99);
+    assert_eq!(
+        client.try_initiate_recovery(),
+        Err(Ok(AdminError::AdminActive))
+    );
+}
+
+#[test]
+fn recovery_cleared_on_admin_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    let config = RecoveryConfig {
+        address: Address::generate(&env),
+        inactivity_ledgers: 100,
+    };
+
+    client.set_recovery(&Some(config.clone()));
+    client.set_admin(&new_admin);
+    assert_eq!(client.recovery(), None);
+
+    client.set_recovery(&Some(config));
+    client.propose_admin(&admin, &None);
+    client.accept_admin();
+    assert_eq!(client.recovery(), None);
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 100);
+    assert_eq!(
+        client.try_initiate_recovery(),
+        Err(Ok(AdminError::NoRecovery))
+    );
+}
+
+mod guardian {
+    use admin_sep::{
+        Admin, Administratable, AdministratableExt, Constructable, Constructor, Guardian,
+        GuardianExt, GuardianRole, HasAdmin, HasGuardian, Pausable, Pauser, derive_contract,
+    };
+    use soroban_sdk::{Address, Env, contract, contracttype};
+
+    #[contract]
+    #[derive_contract(
+        Administratable,
+        Guardian(ext = AdministratableExt),
+        Pausable(ext = GuardianExt),
+        Constructable(ext = AdministratableExt, ext = GuardianExt, args = GuardedArgs)
+    )]
+    pub struct GuardedContract;
+
+    #[contracttype]
+    #[derive(HasAdmin)]
+    pub struct GuardedArgs {
+        #[admin]
+        pub admin: Address,
+        pub guardian: Address,
+        pub live_until_ledger: u32,
+    }
+
+    impl HasGuardian for GuardedArgs {
+        fn guardian(&self) -> &Address {
+            &self.guardian
+        }
+        fn guardian_live_until_ledger(&self) -> Option<u32> {
+            Some(self.live_until_ledger)
+        }
+    }
+}
+
+#[test]
+fn guardian() {
+    use guardian::{GuardedArgs, GuardedContract, GuardedContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let live_until_ledger = env.ledger().sequence() + 10;
+    let args = GuardedArgs {
+        admin: admin.clone(),
+        guardian: guardian.clone(),
+        live_until_ledger,
+    };
+    let contract_id = env.register(GuardedContract, (args,));
+    let client = GuardedContractClient::new(&env, &contract_id);
+    assert_eq!(client.guardian(), Some(guardian.clone()));
+
+    client.pause(&guardian);
+    assert_eq!(
+        client.try_unpause(&guardian),
+        Err(Ok(AdminError::Unauthorized))
+    );
+    client.unpause(&admin);
+
+    env.ledger().set_sequence_number(live_until_ledger + 1);
+    assert_eq!(
+        client.try_pause(&guardian),
+        Err(Ok(AdminError::GuardianWindowClosed))
+    );
+    client.pause(&admin);
+}
+
+mod namespace {
+    use admin_sep::{
+        Admin, Administratable, AdministratableExt, Constructable, Constructor, Instance,
+        Namespace, Pausable, Pauser, derive_contract,
+    };
+    use soroban_sdk::{Env, contract};
+
+    pub struct MyNamespace;
+
+    impl Namespace for MyNamespace {
+        const NAMESPACE: &'static str = "mine";
+    }
+
+    #[contract]
+    #[derive_contract(
+        Administratable(default = Admin<Instance, MyNamespace>),
+        Pausable(ext = AdministratableExt),
+        Constructable(ext = AdministratableExt)
+    )]
+    pub struct NamespacedContract;
+}
+
+#[test]
+fn storage_namespace() {
+    use admin_sep::{AdministratableKey, PausableKey};
+    use namespace::{NamespacedContract, NamespacedContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(NamespacedContract, (admin.clone(),));
+    let client = NamespacedContractClient::new(&env, &contract_id);
+    client.pause(&admin);
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        let admin_key = (Symbol::new(&env, "mine"), AdministratableKey::Admin);
+        assert_eq!(storage.get::<_, Address>(&admin_key), Some(admin));
+        let paused_key = (Symbol::new(&env, "admin_sep"), PausableKey::Paused);
+        assert!(storage.has(&paused_key));
+    });
+}
+
+mod persistent {
+    use admin_sep::{
+        Admin, Administratable, AdministratableExt, Constructable, Constructor, Persistent,
+        derive_contract,
+    };
+    use soroban_sdk::{Env, contract};
+
+    #[contract]
+    #[derive_contract(
+        Administratable(default = Admin<Persistent>),
+        Constructable(ext = AdministratableExt)
+    )]
+    pub struct PersistentContract;
+}
+
+#[test]
+fn persistent_admin() {
+    use admin_sep::AdministratableKey;
+    use persistent::{PersistentContract, PersistentContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let contract_id = env.register(PersistentContract, (admin,));
+    let client = PersistentContractClient::new(&env, &contract_id);
+    client.set_admin(&new_admin);
+    assert_eq!(client.admin(), new_admin);
+
+    env.as_contract(&contract_id, || {
+        let key = (Symbol::new(&env, "admin_sep"), AdministratableKey::Admin);
+        assert!(!env.storage().instance().has(&key));
+        let storage = env.storage().persistent();
+        assert_eq!(storage.get::<_, Address>(&key), Some(new_admin));
+        assert_eq!(storage.get_ttl(&key), TtlConfig::DEFAULT.extend_to);
+    });
+}
+
+mod migration {
+    use admin_sep::{
+        Admin, AdminError, Administratable, AdministratableExt, Constructable, Constructor,
+        Migratable, MigratableExt, Migration, Migrator, Namespace, StorageKey, Upgradable,
+        Upgrader, derive_contract, storage_key,
+    };
+    use soroban_sdk::{Env, Symbol, Vec, contract, contracttype, vec};
+
+    pub struct Migrating;
+
+    impl Namespace for Migrating {
+        const NAMESPACE: &'static str = "migrating";
+    }
+
+    #[contracttype]
+    #[derive(Clone, StorageKey)]
+    pub enum MigratingKey {
+        Steps,
+    }
+
+    pub fn steps_key(env: &Env) -> (Symbol, MigratingKey) {
+        storage_key::<Migrating, _>(env, MigratingKey::Steps)
+    }
+
+    #[contract]
+    #[derive_contract(
+        storage_keys = [(Migrating, MigratingKey)],
+        Administratable,
+        Migratable(ext = AdministratableExt, default = Migrator<MigratingContract>),
+        Upgradable(ext = AdministratableExt, ext = MigratableExt),
+        Constructable(ext = AdministratableExt, ext = MigratableExt)
+    )]
+    pub struct MigratingContract;
+
+    impl Migration for MigratingContract {
+        const VERSION: u32 = 2;
+        fn migrate(env: &Env, from_version: u32) -> Result<(), AdminError> {
+            let key = steps_key(env);
+            let mut steps: Vec<u32> = env.storage().instance().get(&key).unwrap_or(vec![env]);
+            steps.push_back(from_version);
+            env.storage().instance().set(&key, &steps);
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn migrate() {
+    use admin_sep::MigratableKey;
+    use migration::{MigratingContract, MigratingContractClient, steps_key};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(MigratingContract, (admin.clone(),));
+    let client = MigratingContractClient::new(&env, &contract_id);
+    assert_eq!(client.version(), 2);
+    assert_eq!(
+        client.try_migrate(&2),
+        Err(Ok(AdminError::InvalidMigration))
+    );
+
+    // Storage left behind by an older version of the code.
+    env.as_contract(&contract_id, || {
+        let key = (Symbol::new(&env, "admin_sep"), MigratableKey::Version);
+        env.storage().instance().set(&key, &0u32);
+    });
+    let wasm_hash = BytesN::from_array(&env, &[0; 32]);
+    assert_eq!(
+        client.try_upgrade(&wasm_hash, &admin),
+        Err(Ok(AdminError::NotMigrated))
+    );
+    assert_eq!(
+        client.try_migrate(&1),
+        Err(Ok(AdminError::InvalidMigration))
+    );
+
+    client.migrate(&0);
+    client.migrate(&1);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, events::MIGRATED),).into_val(&env),
+                (1u32, 2u32).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.version(), 2);
+    assert_eq!(
+        client.try_migrate(&1),
+        Err(Ok(AdminError::InvalidMigration))
+    );
+    env.as_contract(&contract_id, || {
+        let steps: Vec<u32> = env.storage().instance().get(&steps_key(&env)).unwrap();
+        assert_eq!(steps, vec![&env, 0, 1]);
+    });
+}
+
+mod staged {
+    use admin_sep::{
+        Admin, Administratable, AdministratableExt, Constructable, Constructor, Pausable,
+        PausableExt, Pauser, StagedUpgradable, StagedUpgrader, Upgradable, derive_contract,
+    };
+    use soroban_sdk::{Env, contract};
+
+    pub const DELAY: u32 = 10;
+
+    // Only `Upgradable` is paused, `commit_upgrade` goes through its extensions.
+    #[contract]
+    #[derive_contract(
+        Administratable,
+        Pausable(ext = AdministratableExt),
+        Upgradable(
+            ext = AdministratableExt,
+            ext = PausableExt,
+            default = StagedUpgrader<StagedContract, DELAY>
+        ),
+        StagedUpgradable(ext = AdministratableExt, default = StagedUpgrader<StagedContract, DELAY>),
+        Constructable(ext = AdministratableExt)
+    )]
+    pub struct StagedContract;
+}
+
+#[test]
+fn staged_upgrade() {
+    use admin_sep::PendingUpgrade;
+    use staged::{DELAY, StagedContract, StagedContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StagedContract, (admin.clone(),));
+    let client = StagedContractClient::new(&env, &contract_id);
+    let wasm_hash = upload_wasm(&env);
+    assert_eq!(
+        client.try_upgrade(&wasm_hash, &admin),
+        Err(Ok(AdminError::NoPendingUpgrade))
+    );
+
+    client.propose_upgrade(&wasm_hash, &admin);
+    let ready_ledger = env.ledger().sequence() + DELAY;
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, events::UPGRADE_PROPOSED),).into_val(&env),
+                (wasm_hash.clone(), ready_ledger).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(
+        client.pending_upgrade(),
+        Some(PendingUpgrade {
+            wasm_hash: wasm_hash.clone(),
+            ready_ledger,
+        })
+    );
+    assert_eq!(
+        client.try_commit_upgrade(&admin),
+        Err(Ok(AdminError::UpgradeNotReady))
+    );
+    client.cancel_upgrade(&admin);
+    assert_eq!(client.pending_upgrade(), None);
+    assert_eq!(
+        client.try_cancel_upgrade(&admin),
+        Err(Ok(AdminError::NoPendingUpgrade))
+    );
+
+    client.propose_upgrade(&wasm_hash, &admin);
+    env.ledger().set_sequence_number(ready_ledger);
+    assert_eq!(
+        client.try_upgrade(&BytesN::from_array(&env, &[1; 32]), &admin),
+        Err(Ok(AdminError::NoPendingUpgrade))
+    );
+    client.pause(&admin);
+    assert_eq!(
+        client.try_commit_upgrade(&admin),
+        Err(Ok(AdminError::Paused))
+    );
+    client.unpause(&admin);
+    client.commit_upgrade(&admin);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            <StagedContract as admin_sep::StagedUpgradable>::pending_upgrade(&env),
+            None
+        );
+        assert_eq!(
+            <StagedContract as Upgradable>::current_wasm_hash(&env),
+            Some(wasm_hash.clone())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        <StagedContract as Upgradable>::lock_upgrades(&env, &admin).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            <StagedContract as admin_sep::StagedUpgradable>::propose_upgrade(
+                &env,
+                wasm_hash.clone(),
+                &admin
+            ),
+            Err(AdminError::UpgradesLocked)
+        );
+    });
+}
+
+mod allowlist {
+    use admin_sep::{
+        APPROVER_ROLE, AccessControl, AccessControlExt, Admin, Administratable, AdministratableExt,
+        ApprovedWasms, Constructable, Constructor, HasAdmin, HasRoles, HasWasmHash, Roles,
+        Upgradable, UpgradableExt, UpgradeAllowlist, UpgradeAllowlistExt, Upgrader,
+        derive_contract,
+    };
+    use soroban_sdk::{Address, BytesN, Env, Symbol, Vec, contract, contracttype, vec};
+
+    #[contract]
+    #[derive_contract(
+        Administratable,
+        AccessControl(ext = AdministratableExt),
+        UpgradeAllowlist(ext = AccessControlExt),
+        Upgradable(ext = AdministratableExt, ext = UpgradeAllowlistExt),
+        Constructable(
+            ext = AdministratableExt,
+            ext = AccessControlExt,
+            ext = UpgradableExt,
+            args = AllowlistArgs
+        )
+    )]
+    pub struct AllowlistContract;
+
+    #[contracttype]
+    #[derive(HasAdmin)]
+    pub struct AllowlistArgs {
+        #[admin]
+        pub admin: Address,
+        pub approver: Address,
+        pub wasm_hash: BytesN<32>,
+    }
+
+    impl HasRoles for AllowlistArgs {
+        fn roles(&self, env: &Env) -> Vec<(Symbol, Address)> {
+            vec![env, (APPROVER_ROLE, self.approver.clone())]
+        }
+    }
+
+    impl HasWasmHash for AllowlistArgs {
+        fn wasm_hash(&self) -> &BytesN<32> {
+            &self.wasm_hash
+        }
+    }
+}
+
+#[test]
+fn upgrade_allowlist() {
+    use admin_sep::{APPROVER_ROLE, UpgradeAllowlist};
+    use allowlist::{AllowlistArgs, AllowlistContract, AllowlistContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let approver = Address::generate(&env);
+    let initial_wasm_hash = upload_other_wasm(&env);
+    let args = AllowlistArgs {
+        admin: admin.clone(),
+        approver: approver.clone(),
+        wasm_hash: initial_wasm_hash.clone(),
+    };
+    let contract_id = env.register(AllowlistContract, (args,));
+    let client = AllowlistContractClient::new(&env, &contract_id);
+    let wasm_hash = upload_wasm(&env);
+    assert!(client.has_role(&APPROVER_ROLE, &approver));
+
+    client.approve_wasm(&approver, &initial_wasm_hash);
+    assert_eq!(
+        client.try_upgrade(&initial_wasm_hash, &admin),
+        Err(Ok(AdminError::WasmAlreadyInstalled))
+    );
+    let key = admin_sep::storage_key::<admin_sep::AdminSep, _>(
+        &env,
+        admin_sep::UpgradeAllowlistKey::ApprovedWasm(initial_wasm_hash),
+    );
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().persistent().has(&key));
+    });
+
+    assert_eq!(
+        client.try_approve_wasm(&admin, &wasm_hash),
+        Err(Ok(AdminError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_upgrade(&wasm_hash, &admin),
+        Err(Ok(AdminError::WasmNotApproved))
+    );
+
+    client.approve_wasm(&approver, &wasm_hash);
+    assert!(client.is_wasm_approved(&wasm_hash));
+    client.upgrade(&wasm_hash, &admin);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            <AllowlistContract as Upgradable>::upgrade(&env, wasm_hash.clone(), &admin),
+            Err(AdminError::WasmAlreadyInstalled)
+        );
+        <AllowlistContract as UpgradeAllowlist>::revoke_wasm(&env, &approver, &wasm_hash).unwrap();
+        assert!(!<AllowlistContract as UpgradeAllowlist>::is_wasm_approved(
+            &env, &wasm_hash
+        ));
+    });
+}
+
+#[test]
+fn lock_upgrades() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    let wasm_hash = upload_wasm(&env);
+    assert!(client.is_upgradable());
+
+    client.lock_upgrades(&admin);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, events::UPGRADES_LOCKED),).into_val(&env),
+                admin.into_val(&env),
+            ),
+        ]
+    );
+    assert!(!client.is_upgradable());
+    assert_eq!(
+        client.try_upgrade(&wasm_hash, &admin),
+        Err(Ok(AdminError::UpgradesLocked))
+    );
+    assert_eq!(
+        client.try_lock_upgrades(&admin),
+        Err(Ok(AdminError::UpgradesLocked))
+    );
+}
+
+mod initial_hash {
+    use admin_sep::{
+        Admin, Administratable, AdministratableExt, Constructable, Constructor, HasAdmin,
+        HasWasmHash, Upgradable, UpgradableExt, Upgrader, derive_contract,
+    };
+    use soroban_sdk::{Address, BytesN, Env, contract, contracttype};
+
+    #[contracttype]
+    #[derive(HasAdmin)]
+    pub struct InitArgs {
+        #[admin]
+        pub admin: Address,
+        pub wasm_hash: BytesN<32>,
+    }
+
+    impl HasWasmHash for InitArgs {
+        fn wasm_hash(&self) -> &BytesN<32> {
+            &self.wasm_hash
+        }
+    }
+
+    #[contract]
+    #[derive_contract(
+        Administratable,
+        Upgradable(ext = AdministratableExt),
+        Constructable(ext = AdministratableExt, ext = UpgradableExt, args = InitArgs)
+    )]
+    pub struct InitialHashContract;
+}
+
+#[test]
+fn rollback_first_upgrade() {
+    use initial_hash::{InitArgs, InitialHashContract, InitialHashContractClient};
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let first = upload_wasm(&env);
+    let second = upload_other_wasm(&env);
+    let args = InitArgs {
+        admin: admin.clone(),
+        wasm_hash: first.clone(),
+    };
+    let contract_id = env.register(InitialHashContract, (args,));
+    let client = InitialHashContractClient::new(&env, &contract_id);
+    assert_eq!(client.current_wasm_hash(), Some(first.clone()));
+    assert_eq!(client.rollback_target(), None);
+
+    client.upgrade(&second, &admin);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            <InitialHashContract as Upgradable>::rollback_target(&env),
+            Some(first.clone())
+        );
+        let history = <InitialHashContract as Upgradable>::upgrade_history(&env, 0, 1);
+        assert_eq!(
+            history.get(0).unwrap().old_wasm_hash,
+            Some(first.clone().into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        <InitialHashContract as Upgradable>::rollback(&env, &admin).unwrap();
+        assert_eq!(
+            <InitialHashContract as Upgradable>::current_wasm_hash(&env),
+            Some(first.clone())
+        );
+    });
+}
+
+#[test]
+fn rollback() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(Contract, (CustomArgs(admin.clone(), 1),));
+    let client = ContractClient::new(&env, &contract_id);
+    let first = upload_wasm(&env);
+    let second = upload_other_wasm(&env);
+    assert_eq!(
+        client.try_rollback(&admin),
+        Err(Ok(AdminError::NoRollbackTarget))
+    );
+
+    // `Contract` doesn't record its initial hash, so its first upgrade can't be rolled back.
+    client.upgrade(&first, &admin);
+    env.as_contract(&contract_id, || {
+        assert_eq!(<Contract as Upgradable>::rollback_target(&env), None);
+        <Contract as Upgradable>::upgrade(&env, second.clone(), &admin).unwrap();
+        assert_eq!(
+            <Contract as Upgradable>::rollback_target(&env),
+            Some(first.clone())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        <Contract as Upgradable>::rollback(&env, &admin).unwrap();
+        assert_eq!(
+            <Contract as Upgradable>::current_wasm_hash(&env),
+            Some(first.clone())
+        );
+        assert_eq!(<Contract as Upgradable>::rollback_target(&env), None);
+        let history = <Contract as Upgradable>::upgrade_history(&env, 2, 1);
+        assert_eq!(history.get(0).unwrap().old_wasm_hash, Some(second.into()));
+    });
+}
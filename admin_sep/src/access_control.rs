@@ -0,0 +1,245 @@
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Namespace, Persistent, StorageKey, StorageKeys, Tier, contracttrait,
+    events, storage_key,
+};
+use soroban_sdk::{Address, Env, Symbol, Val, Vec, contracttype, symbol_short};
+
+use crate::administratable::{Administratable, AdministratableExt};
+use crate::pausable::{Pausable, PausableExt};
+
+/// Trait for granting roles to addresses, so access can be split into e.g. minter,
+/// pauser and upgrader roles rather than a single admin.
+///
+/// Each role can have a role admin: members of the role admin may grant and revoke the role.
+/// Roles without a role admin are managed by the extension alone, e.g. the admin of
+/// `AdministratableExt`.
+///
+/// Members are kept in persistent storage, one entry per role and account, so there is no
+/// limit to their number and they are only loaded when checked.
+#[contracttrait(
+    default = Roles,
+    extension_required = true,
+    is_extension = true,
+    init_required = true
+)]
+pub trait AccessControl {
+    fn has_role(env: &Env, role: &soroban_sdk::Symbol, account: &soroban_sdk::Address) -> bool;
+    fn role_admin(env: &Env, role: &soroban_sdk::Symbol) -> Option<soroban_sdk::Symbol>;
+    fn set_role_admin(
+        env: &Env,
+        role: &soroban_sdk::Symbol,
+        admin_role: Option<soroban_sdk::Symbol>,
+    ) -> Result<(), crate::AdminError>;
+    /// Grant `role` to `account`, `caller` must be the admin or hold the role's admin role.
+    fn grant_role(
+        env: &Env,
+        caller: &soroban_sdk::Address,
+        role: &soroban_sdk::Symbol,
+        account: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+    /// Revoke `role` from `account`, `caller` must be the admin or hold the role's admin role.
+    fn revoke_role(
+        env: &Env,
+        caller: &soroban_sdk::Address,
+        role: &soroban_sdk::Symbol,
+        account: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+    /// Give up `role`, requires auth from `account`.
+    fn renounce_role(
+        env: &Env,
+        role: &soroban_sdk::Symbol,
+        account: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+
+    /// Require that `account` holds `role` and authorized the invocation.
+    #[internal]
+    fn require_role(
+        env: &Env,
+        role: &soroban_sdk::Symbol,
+        account: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError> {
+        if !Self::has_role(env, role, account) {
+            return Err(crate::AdminError::Unauthorized);
+        }
+        account.require_auth();
+        Ok(())
+    }
+
+    /// Grant the initial `(role, account)` pairs.
+    #[internal]
+    fn init(
+        env: &Env,
+        roles: &soroban_sdk::Vec<(soroban_sdk::Symbol, soroban_sdk::Address)>,
+    ) -> Result<(), crate::AdminError>;
+}
+
+/// Extract the initial members of roles from a contract's constructor arguments.
+pub trait HasRoles {
+    /// `(role, account)` pairs to grant.
+    fn roles(&self, env: &Env) -> Vec<(Symbol, Address)>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum AccessControlKey {
+    /// Membership of an account in a role, in persistent storage.
+    Role(Symbol, Address),
+    RoleAdmin(Symbol),
+}
+
+/// Role required to `upgrade` through `AccessControlExt`.
+pub const UPGRADER_ROLE: Symbol = symbol_short!("upgrader");
+
+/// Role required to approve wasm hashes through `AccessControlExt`.
+pub const APPROVER_ROLE: Symbol = symbol_short!("approver");
+
+/// Role required to `pause` and `unpause` through `AccessControlExt`.
+pub const PAUSER_ROLE: Symbol = symbol_short!("pauser");
+
+/// Role for a contract's own minting methods, checked with `require_role`.
+pub const MINTER_ROLE: Symbol = symbol_short!("minter");
+
+pub struct Roles<K = AdminSep>(PhantomData<K>);
+
+impl<K: Namespace> AccessControl for Roles<K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, AccessControlKey::VARIANTS)];
+    fn has_role(env: &Env, role: &Symbol, account: &Address) -> bool {
+        let key = storage_key::<K, _>(env, AccessControlKey::Role(role.clone(), account.clone()));
+        Persistent::get::<_, ()>(env, &key).is_some()
+    }
+
+    fn role_admin(env: &Env, role: &Symbol) -> Option<Symbol> {
+        env.storage().instance().get(&storage_key::<K, _>(
+            env,
+            AccessControlKey::RoleAdmin(role.clone()),
+        ))
+    }
+
+    fn set_role_admin(
+        env: &Env,
+        role: &Symbol,
+        admin_role: Option<Symbol>,
+    ) -> Result<(), AdminError> {
+        let key = storage_key::<K, _>(env, AccessControlKey::RoleAdmin(role.clone()));
+        match admin_role {
+            Some(admin_role) => env.storage().instance().set(&key, &admin_role),
+            None => env.storage().instance().remove(&key),
+        }
+        Ok(())
+    }
+
+    fn grant_role(
+        env: &Env,
+        caller: &Address,
+        role: &Symbol,
+        account: &Address,
+    ) -> Result<(), AdminError> {
+        let key = storage_key::<K, _>(env, AccessControlKey::Role(role.clone(), account.clone()));
+        Persistent::set(env, &key, &());
+        events::role_granted(env, role, account, caller);
+        Ok(())
+    }
+
+    fn revoke_role(
+        env: &Env,
+        caller: &Address,
+        role: &Symbol,
+        account: &Address,
+    ) -> Result<(), AdminError> {
+        if !Self::has_role(env, role, account) {
+            return Err(AdminError::RoleNotHeld);
+        }
+        let key = storage_key::<K, _>(env, AccessControlKey::Role(role.clone(), account.clone()));
+        Persistent::remove(env, &key);
+        events::role_revoked(env, role, account, caller);
+        Ok(())
+    }
+
+    fn renounce_role(env: &Env, role: &Symbol, account: &Address) -> Result<(), AdminError> {
+        account.require_auth();
+        Self::revoke_role(env, account, role, account)
+    }
+
+    fn init(env: &Env, roles: &Vec<(Symbol, Address)>) -> Result<(), AdminError> {
+        let caller = env.current_contract_address();
+        for (role, account) in roles.iter() {
+            Self::grant_role(env, &caller, &role, &account)?;
+        }
+        Ok(())
+    }
+}
+
+/// Authorize `caller` to manage `role`: either the admin, authorizing `args`, or a member of
+/// the role's admin role.
+fn require_role_manager<T: Administratable, N: AccessControl>(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    args: Vec<Val>,
+) -> Result<(), AdminError> {
+    if T::try_admin(env)? == *caller {
+        return T::require_admin_for_args(env, args);
+    }
+    let admin_role = N::role_admin(env, role).ok_or(AdminError::Unauthorized)?;
+    N::require_role(env, &admin_role, caller)
+}
+
+impl<T: Administratable, N: AccessControl> AccessControl for AdministratableExt<T, N> {
+    type Impl = N;
+    fn set_role_admin(
+        env: &Env,
+        role: &Symbol,
+        admin_role: Option<Symbol>,
+    ) -> Result<(), AdminError> {
+        T::require_admin_for_args(env, Self::set_role_admin_auth_args(env, role, &admin_role))?;
+        N::set_role_admin(env, role, admin_role)
+    }
+
+    fn grant_role(
+        env: &Env,
+        caller: &Address,
+        role: &Symbol,
+        account: &Address,
+    ) -> Result<(), AdminError> {
+        let args = Self::grant_role_auth_args(env, role, account);
+        require_role_manager::<T, N>(env, caller, role, args)?;
+        N::grant_role(env, caller, role, account)
+    }
+
+    fn revoke_role(
+        env: &Env,
+        caller: &Address,
+        role: &Symbol,
+        account: &Address,
+    ) -> Result<(), AdminError> {
+        let args = Self::revoke_role_auth_args(env, role, account);
+        require_role_manager::<T, N>(env, caller, role, args)?;
+        N::revoke_role(env, caller, role, account)
+    }
+}
+
+/// Roles can still be revoked and renounced while paused, to cut off a compromised account.
+impl<T: Pausable, N: AccessControl> AccessControl for PausableExt<T, N> {
+    type Impl = N;
+    fn set_role_admin(
+        env: &Env,
+        role: &Symbol,
+        admin_role: Option<Symbol>,
+    ) -> Result<(), AdminError> {
+        T::require_not_paused(env)?;
+        N::set_role_admin(env, role, admin_role)
+    }
+
+    fn grant_role(
+        env: &Env,
+        caller: &Address,
+        role: &Symbol,
+        account: &Address,
+    ) -> Result<(), AdminError> {
+        T::require_not_paused(env)?;
+        N::grant_role(env, caller, role, account)
+    }
+}
@@ -1,43 +1,244 @@
-use crate::contracttrait;
-use soroban_sdk::{Address, Env, Symbol, symbol_short};
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Instance, Namespace, StorageKey, StorageKeys, Tier, contracttrait,
+    events, storage_key,
+};
+use soroban_sdk::{Address, Env, Symbol, Val, Vec, contracttype, symbol_short};
 
 /// Trait for using an admin address to control access.
-#[contracttrait(default = Admin, is_extension = true)]
+#[contracttrait(default = Admin, is_extension = true, init_required = true)]
 pub trait Administratable {
     fn admin(env: &Env) -> soroban_sdk::Address;
-    fn set_admin(env: &Env, new_admin: &soroban_sdk::Address);
+    fn set_admin(env: &Env, new_admin: &soroban_sdk::Address) -> Result<(), crate::AdminError>;
+
+    /// Nominate `new_admin` to take over, optionally only until `live_until_ledger`.
+    /// The nomination takes effect once `new_admin` calls `accept_admin`.
+    fn propose_admin(
+        env: &Env,
+        new_admin: &soroban_sdk::Address,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), crate::AdminError>;
+    /// Accept the pending nomination, requires auth from the nominated address.
+    fn accept_admin(env: &Env) -> Result<(), crate::AdminError>;
+    fn cancel_admin_transfer(env: &Env) -> Result<(), crate::AdminError>;
+    fn pending_admin(env: &Env) -> Option<crate::PendingAdmin>;
+
+    /// The current admin, or `AdminError::NotInitialized` if none was ever set.
+    #[internal]
+    fn try_admin(env: &Env) -> Result<soroban_sdk::Address, crate::AdminError>;
 
+    /// Require auth from the admin, also extending the TTL of the contract instance and
+    /// recording a heartbeat for `Recoverable`.
     #[internal]
-    fn require_admin(env: &Env) {
-        Self::admin(env).require_auth();
+    fn require_admin(env: &Env) -> Result<(), crate::AdminError> {
+        Self::try_admin(env)?.require_auth();
+        crate::extend_instance_ttl(env);
+        crate::recoverable::record_activity(env);
+        Ok(())
     }
 
+    /// Like `require_admin`, but binds the admin's auth to `args` rather than to all arguments
+    /// of the invocation, e.g. only the `wasm_hash` of an `upgrade`. An admin that is a
+    /// contract account can then authorize per method and argument.
+    ///
+    /// Every method of the default implementations and extensions in this crate requires the
+    /// admin this way, with the `<method>_auth_args` generated by `#[contracttrait]`, i.e. the
+    /// method's arguments other than the caller; `require_admin` is left for the contract's
+    /// own methods.
     #[internal]
-    fn init(env: &Env, admin: &soroban_sdk::Address);
+    fn require_admin_for_args(
+        env: &Env,
+        args: soroban_sdk::Vec<soroban_sdk::Val>,
+    ) -> Result<(), crate::AdminError> {
+        Self::try_admin(env)?.require_auth_for_args(args);
+        crate::extend_instance_ttl(env);
+        crate::recoverable::record_activity(env);
+        Ok(())
+    }
+
+    /// Nominate `new_admin` like `propose_admin` but without requiring the admin's auth,
+    /// for extensions that check their own conditions, e.g. `Recoverable`.
+    #[internal]
+    fn nominate_admin(
+        env: &Env,
+        new_admin: &soroban_sdk::Address,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), crate::AdminError>;
+
+    #[internal]
+    fn init(env: &Env, admin: &soroban_sdk::Address) -> Result<(), crate::AdminError>;
 }
 
-pub const STORAGE_KEY: Symbol = symbol_short!("A");
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum AdministratableKey {
+    Admin,
+    PendingAdmin,
+}
 
-fn get(env: &Env) -> Option<Address> {
-    env.storage().instance().get(&STORAGE_KEY)
+/// An admin nomination waiting to be accepted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAdmin {
+    pub address: Address,
+    /// Last ledger on which the nomination can be accepted, if it expires at all.
+    pub live_until_ledger: Option<u32>,
 }
 
-pub struct Admin;
+/// Instance storage key of the admin before keys were namespaced.
+pub const LEGACY_STORAGE_KEY: Symbol = symbol_short!("A");
+
+fn get<T: Tier, K: Namespace>(env: &Env) -> Option<Address> {
+    T::get(env, &storage_key::<K, _>(env, AdministratableKey::Admin))
+        .or_else(|| migrate_legacy::<T, K>(env))
+}
+
+/// Move an admin stored under `LEGACY_STORAGE_KEY` to its namespaced key, so a contract
+/// upgraded from an earlier version keeps its admin. Only the default namespace had one.
+fn migrate_legacy<T: Tier, K: Namespace>(env: &Env) -> Option<Address> {
+    if K::NAMESPACE != AdminSep::NAMESPACE {
+        return None;
+    }
+    let admin: Address = env.storage().instance().get(&LEGACY_STORAGE_KEY)?;
+    env.storage().instance().remove(&LEGACY_STORAGE_KEY);
+    set::<T, K>(env, &admin);
+    Some(admin)
+}
+
+fn set<T: Tier, K: Namespace>(env: &Env, admin: &Address) {
+    T::set(
+        env,
+        &storage_key::<K, _>(env, AdministratableKey::Admin),
+        admin,
+    );
+}
+
+pub(crate) fn get_pending<T: Tier, K: Namespace>(env: &Env) -> Option<PendingAdmin> {
+    T::get(
+        env,
+        &storage_key::<K, _>(env, AdministratableKey::PendingAdmin),
+    )
+}
+
+pub(crate) fn set_pending<T: Tier, K: Namespace>(
+    env: &Env,
+    new_admin: &Address,
+    live_until_ledger: Option<u32>,
+) {
+    let pending = PendingAdmin {
+        address: new_admin.clone(),
+        live_until_ledger,
+    };
+    T::set(
+        env,
+        &storage_key::<K, _>(env, AdministratableKey::PendingAdmin),
+        &pending,
+    );
+}
 
-impl Administratable for Admin {
-    type Impl = Admin;
+pub(crate) fn remove_pending<T: Tier, K: Namespace>(env: &Env) {
+    T::remove(
+        env,
+        &storage_key::<K, _>(env, AdministratableKey::PendingAdmin),
+    );
+}
+
+/// Remove the pending nomination once it's checked to be live and authorized by the nominee.
+pub(crate) fn take_pending<T: Tier, K: Namespace>(env: &Env) -> Result<Address, AdminError> {
+    let pending = get_pending::<T, K>(env).ok_or(AdminError::NoPendingAdmin)?;
+    if pending
+        .live_until_ledger
+        .is_some_and(|ledger| env.ledger().sequence() > ledger)
+    {
+        return Err(AdminError::PendingAdminExpired);
+    }
+    pending.address.require_auth();
+    remove_pending::<T, K>(env);
+    Ok(pending.address)
+}
+
+/// Require that `caller` is the admin and authorized the invocation with `args`, the
+/// invocation's arguments other than `caller` itself.
+pub fn require_admin_caller<T: Administratable>(
+    env: &Env,
+    caller: &Address,
+    args: Vec<Val>,
+) -> Result<(), AdminError> {
+    if T::try_admin(env)? != *caller {
+        return Err(AdminError::Unauthorized);
+    }
+    T::require_admin_for_args(env, args)
+}
+
+/// The default `Administratable`, storing the admin in storage tier `T`, e.g. `Admin<Persistent>`.
+pub struct Admin<T = Instance, K = AdminSep>(PhantomData<(T, K)>);
+
+impl<T: Tier, K: Namespace> Administratable for Admin<T, K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, AdministratableKey::VARIANTS)];
     fn admin(env: &Env) -> soroban_sdk::Address {
-        unsafe { get(env).unwrap_unchecked() }
+        Self::try_admin(env).unwrap_or_else(|e| env.panic_with_error(e))
+    }
+    fn try_admin(env: &Env) -> Result<soroban_sdk::Address, AdminError> {
+        get::<T, K>(env).ok_or(AdminError::NotInitialized)
     }
-    fn set_admin(env: &Env, new_admin: &soroban_sdk::Address) {
-        Self::require_admin(env);
-        env.storage().instance().set(&STORAGE_KEY, &new_admin);
+    fn set_admin(env: &Env, new_admin: &soroban_sdk::Address) -> Result<(), AdminError> {
+        Self::require_admin_for_args(env, Self::set_admin_auth_args(env, new_admin))?;
+        let old_admin = Self::try_admin(env)?;
+        set::<T, K>(env, new_admin);
+        remove_pending::<T, K>(env);
+        crate::recoverable::clear(env);
+        events::admin_set(env, &old_admin, new_admin);
+        Ok(())
+    }
+
+    fn propose_admin(
+        env: &Env,
+        new_admin: &soroban_sdk::Address,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), AdminError> {
+        Self::require_admin_for_args(
+            env,
+            Self::propose_admin_auth_args(env, new_admin, &live_until_ledger),
+        )?;
+        Self::nominate_admin(env, new_admin, live_until_ledger)
+    }
+
+    fn accept_admin(env: &Env) -> Result<(), AdminError> {
+        let new_admin = take_pending::<T, K>(env)?;
+        let old_admin = Self::try_admin(env)?;
+        set::<T, K>(env, &new_admin);
+        crate::recoverable::clear(env);
+        events::admin_set(env, &old_admin, &new_admin);
+        Ok(())
+    }
+
+    fn cancel_admin_transfer(env: &Env) -> Result<(), AdminError> {
+        Self::require_admin_for_args(env, Self::cancel_admin_transfer_auth_args(env))?;
+        remove_pending::<T, K>(env);
+        Ok(())
+    }
+
+    fn pending_admin(env: &Env) -> Option<PendingAdmin> {
+        get_pending::<T, K>(env)
+    }
+
+    fn nominate_admin(
+        env: &Env,
+        new_admin: &soroban_sdk::Address,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), AdminError> {
+        set_pending::<T, K>(env, new_admin, live_until_ledger);
+        Ok(())
     }
 
-    fn init(env: &Env, admin: &soroban_sdk::Address) {
-        if get(env).is_some() {
-            panic!("Admin already initialized");
+    fn init(env: &Env, admin: &soroban_sdk::Address) -> Result<(), AdminError> {
+        if get::<T, K>(env).is_some() {
+            return Err(AdminError::AlreadyInitialized);
         }
-        env.storage().instance().set(&STORAGE_KEY, &admin);
+        set::<T, K>(env, admin);
+        events::admin_initialized(env, admin);
+        Ok(())
     }
 }
@@ -1,43 +1,105 @@
+use core::marker::PhantomData;
+
 use soroban_sdk::{Address, Env};
 
+use crate::access_control::{AccessControl, AccessControlExt, HasRoles};
+use crate::administratable::{Administratable, AdministratableExt};
+use crate::guardian::{Guardian, GuardianExt, HasGuardian};
+use crate::migratable::{Migratable, MigratableExt};
+use crate::upgradable::{HasWasmHash, Upgradable, UpgradableExt};
+use crate::{StorageKeys, contracttrait};
+
 pub trait HasAdmin {
     fn admin(&self) -> &Address;
 }
 
-// #[contracttrait(DefaultAdmin)]
-pub trait Constructable<T: HasAdmin = Address>: crate::Administratable {
+/// Trait for the contract's constructor, taking arguments of type `T`.
+///
+/// Each composed trait with an `init` is initialized by wrapping `Constructable` in its
+/// extension, which extracts the trait's initial value from the arguments, e.g.
+/// `Constructable(ext = AdministratableExt, ext = GuardianExt, args = MyArgs)` with
+/// `MyArgs: HasAdmin + HasGuardian`. `derive_contract` fails to compile if `Administratable`,
+/// `AccessControl`, `Guardian` or `Migratable` is composed without its extension here.
+/// Recording the initial wasm hash with `UpgradableExt` is optional. The contract's own
+/// initialization is its `Construct` hook, run last with `default = Constructor<Contract>`.
+#[contracttrait(default = Constructor)]
+pub trait Constructable<T = soroban_sdk::Address> {
+    fn __constructor(env: &Env, args: T);
+}
+
+/// The contract's own initialization, run by `Constructor<Contract>`.
+pub trait Construct<T> {
     #[allow(unused_variables)]
     fn construct(env: &Env, args: T) {}
-    fn constructor(env: &Env, args: T) {
-        Self::init(env, args.admin());
-        Self::construct(env, args);
+}
+
+impl<T> Construct<T> for () {}
+
+/// The default `Constructable`, calling the `Construct` hook of `C`, none by default.
+pub struct Constructor<C = ()>(PhantomData<C>);
+
+impl<T, C: Construct<T>> Constructable<T> for Constructor<C> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[];
+    fn __constructor(env: &Env, args: T) {
+        C::construct(env, args);
     }
 }
 
-// // Generates
-#[macro_export]
-macro_rules! Constructable {
-    ($contract_name:ident) => {
-        impl Constructable<soroban_sdk::Address> for $contract_name {}
-        Constructable!($contract_name, $contract_name, soroban_sdk::Address);
-    };
-    ($contract_name:ident, $impl_name:path, $type_name:ty) => {
-        pub trait ConstructableExt {
-            type Args: HasAdmin;
-            fn __constructor(env: Env, args: Self::Args);
+impl<T: HasAdmin, C: Administratable, N: Constructable<T>> Constructable<T>
+    for AdministratableExt<C, N>
+{
+    type Impl = N;
+    fn __constructor(env: &Env, args: T) {
+        if let Err(e) = C::init(env, args.admin()) {
+            env.panic_with_error(e);
         }
-        #[soroban_sdk::contractimpl]
-        impl ConstructableExt for $contract_name {
-            type Args = $type_name;
-            fn __constructor(env: Env, args: Self::Args) {
-                <$impl_name as Constructable<$type_name>>::constructor(&env, args);
-            }
+        N::__constructor(env, args);
+    }
+}
+
+impl<T: HasGuardian, C: Guardian, N: Constructable<T>> Constructable<T> for GuardianExt<C, N> {
+    type Impl = N;
+    fn __constructor(env: &Env, args: T) {
+        if let Err(e) = C::init(env, args.guardian(), args.guardian_live_until_ledger()) {
+            env.panic_with_error(e);
         }
-    };
-    () => {};
+        N::__constructor(env, args);
+    }
+}
+
+impl<T: HasRoles, C: AccessControl, N: Constructable<T>> Constructable<T>
+    for AccessControlExt<C, N>
+{
+    type Impl = N;
+    fn __constructor(env: &Env, args: T) {
+        if let Err(e) = C::init(env, &args.roles(env)) {
+            env.panic_with_error(e);
+        }
+        N::__constructor(env, args);
+    }
+}
+
+/// A new contract's storage is at the version of its code, with nothing to migrate.
+impl<T, C: Migratable, N: Constructable<T>> Constructable<T> for MigratableExt<C, N> {
+    type Impl = N;
+    fn __constructor(env: &Env, args: T) {
+        if let Err(e) = C::init(env) {
+            env.panic_with_error(e);
+        }
+        N::__constructor(env, args);
+    }
 }
 
-// impl Constructable<Address> for DefaultAdmin {}
+impl<T: HasWasmHash, C: Upgradable, N: Constructable<T>> Constructable<T> for UpgradableExt<C, N> {
+    type Impl = N;
+    fn __constructor(env: &Env, args: T) {
+        if let Err(e) = C::init_wasm_hash(env, args.wasm_hash()) {
+            env.panic_with_error(e);
+        }
+        N::__constructor(env, args);
+    }
+}
 
 impl HasAdmin for Address {
     fn admin(&self) -> &Address {
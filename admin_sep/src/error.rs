@@ -0,0 +1,61 @@
+use soroban_sdk::contracterror;
+
+/// Errors raised by the admin-sep contract traits.
+///
+/// The discriminants are part of the standard, so clients can rely on them across every
+/// contract built on admin-sep. New variants must only ever be appended.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AdminError {
+    /// No admin has been set for this contract.
+    NotInitialized = 1,
+    /// The admin has already been set and cannot be initialized again.
+    AlreadyInitialized = 2,
+    /// The caller is not allowed to perform this operation.
+    Unauthorized = 3,
+    /// There is no admin nomination to accept.
+    NoPendingAdmin = 4,
+    /// The admin nomination is past its `live_until_ledger`.
+    PendingAdminExpired = 5,
+    /// A `TtlConfig` whose `threshold` is greater than its `extend_to`.
+    InvalidTtlConfig = 6,
+    /// The account does not hold the role being revoked.
+    RoleNotHeld = 7,
+    /// A threshold of 0, or one the signers' combined weight cannot reach.
+    InvalidThreshold = 8,
+    /// The operation is not scheduled in the timelock.
+    OperationNotScheduled = 9,
+    /// The operation's delay has not passed yet.
+    OperationNotReady = 10,
+    /// The operation is already scheduled.
+    OperationAlreadyScheduled = 11,
+    /// The delay is shorter than the timelock's minimum delay.
+    DelayTooShort = 12,
+    /// The contract is paused.
+    Paused = 13,
+    /// The contract is not paused.
+    NotPaused = 14,
+    /// The guardian's window for using its powers is closed.
+    GuardianWindowClosed = 15,
+    /// No recovery address is registered.
+    NoRecovery = 16,
+    /// The admin has not been inactive for long enough to recover.
+    AdminActive = 17,
+    /// The storage has not been migrated to the version of the contract's code.
+    NotMigrated = 18,
+    /// The migration is not from the storage's current version, or there is nothing to migrate.
+    InvalidMigration = 19,
+    /// There is no proposed upgrade, or it is for another wasm hash.
+    NoPendingUpgrade = 20,
+    /// The proposed upgrade's delay has not passed yet.
+    UpgradeNotReady = 21,
+    /// The wasm hash is not approved for upgrading to.
+    WasmNotApproved = 22,
+    /// The wasm hash is already the contract's.
+    WasmAlreadyInstalled = 23,
+    /// Upgrades are permanently locked.
+    UpgradesLocked = 24,
+    /// There is no previous wasm hash to roll back to.
+    NoRollbackTarget = 25,
+}
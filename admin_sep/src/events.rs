@@ -0,0 +1,162 @@
+//! Events published by the default implementations.
+//!
+//! Every event has a single topic naming it, so indexers can subscribe to it across all
+//! contracts built on admin-sep:
+//!
+//! | Topic                | Data                                                                   |
+//! | -------------------- |------------------------------------------------------------------------|
+//! | `admin_initialized`  | `admin: Address`                                                       |
+//! | `admin_set`          | `(old_admin: Address, new_admin: Address)`                             |
+//! | `upgraded`           | `(old_wasm_hash: Option<BytesN<32>>, new_wasm_hash: BytesN<32>)`       |
+//! | `role_granted`       | `(role: Symbol, account: Address, caller: Address)`                    |
+//! | `role_revoked`       | `(role: Symbol, account: Address, caller: Address)`                    |
+//! | `op_scheduled`       | `(id: BytesN<32>, fn_name: Symbol, args: Vec<Val>, ready_ledger: u32)` |
+//! | `op_executed`        | `id: BytesN<32>`                                                       |
+//! | `op_cancelled`       | `id: BytesN<32>`                                                       |
+//! | `paused`             | `caller: Address`                                                      |
+//! | `unpaused`           | `caller: Address`                                                      |
+//! | `guardian_set`       | `(old_guardian: Option<Address>, new_guardian: Option<Address>)`       |
+//! | `recovery_initiated` | `recovery: Address`                                                    |
+//! | `migrated`           | `(from_version: u32, to_version: u32)`                                 |
+//! | `upgrade_proposed`   | `(wasm_hash: BytesN<32>, ready_ledger: u32)`                           |
+//! | `upgrade_cancelled`  | `wasm_hash: BytesN<32>`                                                |
+//! | `wasm_approved`      | `(wasm_hash: BytesN<32>, approver: Address)`                           |
+//! | `wasm_revoked`       | `(wasm_hash: BytesN<32>, approver: Address)`                           |
+//! | `upgrades_locked`    | `operator: Address`                                                    |
+//!
+//! `old_wasm_hash` is `None` when the contract has not been upgraded through `Upgradable`
+//! before, since a contract cannot otherwise learn its own wasm hash.
+use soroban_sdk::{Address, BytesN, Env, Symbol, Val, Vec};
+
+pub const ADMIN_INITIALIZED: &str = "admin_initialized";
+pub const ADMIN_SET: &str = "admin_set";
+pub const UPGRADED: &str = "upgraded";
+pub const ROLE_GRANTED: &str = "role_granted";
+pub const ROLE_REVOKED: &str = "role_revoked";
+pub const OPERATION_SCHEDULED: &str = "op_scheduled";
+pub const OPERATION_EXECUTED: &str = "op_executed";
+pub const OPERATION_CANCELLED: &str = "op_cancelled";
+pub const PAUSED: &str = "paused";
+pub const UNPAUSED: &str = "unpaused";
+pub const GUARDIAN_SET: &str = "guardian_set";
+pub const RECOVERY_INITIATED: &str = "recovery_initiated";
+pub const MIGRATED: &str = "migrated";
+pub const UPGRADE_PROPOSED: &str = "upgrade_proposed";
+pub const UPGRADE_CANCELLED: &str = "upgrade_cancelled";
+pub const WASM_APPROVED: &str = "wasm_approved";
+pub const WASM_REVOKED: &str = "wasm_revoked";
+pub const UPGRADES_LOCKED: &str = "upgrades_locked";
+
+pub fn admin_initialized(env: &Env, admin: &Address) {
+    env.events()
+        .publish((Symbol::new(env, ADMIN_INITIALIZED),), admin.clone());
+}
+
+pub fn admin_set(env: &Env, old_admin: &Address, new_admin: &Address) {
+    env.events().publish(
+        (Symbol::new(env, ADMIN_SET),),
+        (old_admin.clone(), new_admin.clone()),
+    );
+}
+
+pub fn upgraded(env: &Env, old_wasm_hash: Option<BytesN<32>>, new_wasm_hash: &BytesN<32>) {
+    env.events().publish(
+        (Symbol::new(env, UPGRADED),),
+        (old_wasm_hash, new_wasm_hash.clone()),
+    );
+}
+
+pub fn role_granted(env: &Env, role: &Symbol, account: &Address, caller: &Address) {
+    env.events().publish(
+        (Symbol::new(env, ROLE_GRANTED),),
+        (role.clone(), account.clone(), caller.clone()),
+    );
+}
+
+pub fn role_revoked(env: &Env, role: &Symbol, account: &Address, caller: &Address) {
+    env.events().publish(
+        (Symbol::new(env, ROLE_REVOKED),),
+        (role.clone(), account.clone(), caller.clone()),
+    );
+}
+
+pub fn operation_scheduled(
+    env: &Env,
+    id: &BytesN<32>,
+    fn_name: &Symbol,
+    args: &Vec<Val>,
+    ready_ledger: u32,
+) {
+    env.events().publish(
+        (Symbol::new(env, OPERATION_SCHEDULED),),
+        (id.clone(), fn_name.clone(), args.clone(), ready_ledger),
+    );
+}
+
+pub fn operation_executed(env: &Env, id: &BytesN<32>) {
+    env.events()
+        .publish((Symbol::new(env, OPERATION_EXECUTED),), id.clone());
+}
+
+pub fn operation_cancelled(env: &Env, id: &BytesN<32>) {
+    env.events()
+        .publish((Symbol::new(env, OPERATION_CANCELLED),), id.clone());
+}
+
+pub fn paused(env: &Env, caller: &Address) {
+    env.events()
+        .publish((Symbol::new(env, PAUSED),), caller.clone());
+}
+
+pub fn unpaused(env: &Env, caller: &Address) {
+    env.events()
+        .publish((Symbol::new(env, UNPAUSED),), caller.clone());
+}
+
+pub fn guardian_set(env: &Env, old_guardian: Option<Address>, new_guardian: Option<Address>) {
+    env.events().publish(
+        (Symbol::new(env, GUARDIAN_SET),),
+        (old_guardian, new_guardian),
+    );
+}
+
+pub fn recovery_initiated(env: &Env, recovery: &Address) {
+    env.events()
+        .publish((Symbol::new(env, RECOVERY_INITIATED),), recovery.clone());
+}
+
+pub fn migrated(env: &Env, from_version: u32, to_version: u32) {
+    env.events()
+        .publish((Symbol::new(env, MIGRATED),), (from_version, to_version));
+}
+
+pub fn upgrade_proposed(env: &Env, wasm_hash: &BytesN<32>, ready_ledger: u32) {
+    env.events().publish(
+        (Symbol::new(env, UPGRADE_PROPOSED),),
+        (wasm_hash.clone(), ready_ledger),
+    );
+}
+
+pub fn upgrade_cancelled(env: &Env, wasm_hash: &BytesN<32>) {
+    env.events()
+        .publish((Symbol::new(env, UPGRADE_CANCELLED),), wasm_hash.clone());
+}
+
+pub fn wasm_approved(env: &Env, wasm_hash: &BytesN<32>, approver: &Address) {
+    env.events().publish(
+        (Symbol::new(env, WASM_APPROVED),),
+        (wasm_hash.clone(), approver.clone()),
+    );
+}
+
+pub fn wasm_revoked(env: &Env, wasm_hash: &BytesN<32>, approver: &Address) {
+    env.events().publish(
+        (Symbol::new(env, WASM_REVOKED),),
+        (wasm_hash.clone(), approver.clone()),
+    );
+}
+
+pub fn upgrades_locked(env: &Env, operator: &Address) {
+    env.events()
+        .publish((Symbol::new(env, UPGRADES_LOCKED),), operator.clone());
+}
@@ -0,0 +1,118 @@
+use crate::{AdminError, AdminSep, Namespace, StorageKey, StorageKeys, contracttrait, storage_key};
+use soroban_sdk::{Env, contracttype};
+
+use crate::administratable::{Administratable, AdministratableExt};
+use crate::pausable::{Pausable, PausableExt};
+
+/// Trait for keeping the contract instance and code from being archived.
+#[contracttrait(default = Extender, extension_required = true)]
+pub trait Extendable {
+    /// Extend the TTL of the contract instance and code according to `ttl_config`.
+    fn extend_ttl(env: &Env) -> Result<(), crate::AdminError>;
+    fn ttl_config(env: &Env) -> crate::TtlConfig;
+    fn set_ttl_config(env: &Env, config: crate::TtlConfig) -> Result<(), crate::AdminError>;
+}
+
+/// Always in the `AdminSep` namespace, since `require_admin` reads it to extend the TTL.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum ExtendableKey {
+    TtlConfig,
+}
+
+pub const DAY_IN_LEDGERS: u32 = 17280;
+
+/// When and how far the TTL of the contract instance and code is extended.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    /// Only extend once the TTL drops below this many ledgers.
+    pub threshold: u32,
+    /// Number of ledgers the TTL is extended to, capped at the network's max TTL.
+    pub extend_to: u32,
+    /// Whether `extend_ttl` can be called by anyone rather than only the admin.
+    pub anyone_can_extend: bool,
+}
+
+impl TtlConfig {
+    pub const DEFAULT: TtlConfig = TtlConfig {
+        threshold: 29 * DAY_IN_LEDGERS,
+        extend_to: 30 * DAY_IN_LEDGERS,
+        anyone_can_extend: true,
+    };
+}
+
+fn get(env: &Env) -> TtlConfig {
+    env.storage()
+        .instance()
+        .get(&storage_key::<AdminSep, _>(env, ExtendableKey::TtlConfig))
+        .unwrap_or(TtlConfig::DEFAULT)
+}
+
+/// The `(threshold, extend_to)` to extend TTLs with according to the stored `TtlConfig`.
+pub(crate) fn ttl_extension(env: &Env) -> (u32, u32) {
+    let TtlConfig {
+        threshold,
+        extend_to,
+        ..
+    } = get(env);
+    let extend_to = extend_to.min(env.storage().max_ttl());
+    (threshold.min(extend_to), extend_to)
+}
+
+/// Extend the TTL of the contract instance and code using the stored `TtlConfig`.
+/// Called on every admin-gated call so an actively administered contract stays live.
+pub fn extend_instance_ttl(env: &Env) {
+    let (threshold, extend_to) = ttl_extension(env);
+    env.storage().instance().extend_ttl(threshold, extend_to);
+}
+
+pub struct Extender;
+
+impl Extendable for Extender {
+    type Impl = Extender;
+    const STORAGE_KEYS: StorageKeys = &[(AdminSep::NAMESPACE, ExtendableKey::VARIANTS)];
+    fn extend_ttl(env: &Env) -> Result<(), AdminError> {
+        extend_instance_ttl(env);
+        Ok(())
+    }
+
+    fn ttl_config(env: &Env) -> TtlConfig {
+        get(env)
+    }
+
+    fn set_ttl_config(env: &Env, config: TtlConfig) -> Result<(), AdminError> {
+        if config.threshold > config.extend_to {
+            return Err(AdminError::InvalidTtlConfig);
+        }
+        env.storage().instance().set(
+            &storage_key::<AdminSep, _>(env, ExtendableKey::TtlConfig),
+            &config,
+        );
+        Ok(())
+    }
+}
+
+impl<T: Administratable, N: Extendable> Extendable for AdministratableExt<T, N> {
+    type Impl = N;
+    fn extend_ttl(env: &Env) -> Result<(), AdminError> {
+        if !N::ttl_config(env).anyone_can_extend {
+            T::require_admin_for_args(env, Self::extend_ttl_auth_args(env))?;
+        }
+        N::extend_ttl(env)
+    }
+
+    fn set_ttl_config(env: &Env, config: TtlConfig) -> Result<(), AdminError> {
+        T::require_admin_for_args(env, Self::set_ttl_config_auth_args(env, &config))?;
+        N::set_ttl_config(env, config)
+    }
+}
+
+/// The TTL can still be extended while paused, so the contract isn't archived meanwhile.
+impl<T: Pausable, N: Extendable> Extendable for PausableExt<T, N> {
+    type Impl = N;
+    fn set_ttl_config(env: &Env, config: TtlConfig) -> Result<(), AdminError> {
+        T::require_not_paused(env)?;
+        N::set_ttl_config(env, config)
+    }
+}
@@ -0,0 +1,151 @@
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Namespace, StorageKey, StorageKeys, contracttrait, events, storage_key,
+};
+use soroban_sdk::{Address, Env, contracttype};
+
+use crate::administratable::{Administratable, AdministratableExt};
+
+/// Trait for a guardian: a hot key with narrowly scoped emergency powers, so the admin key
+/// can stay cold.
+///
+/// The guardian's powers are granted by wrapping a contract trait in `GuardianExt`, e.g.
+/// `Pausable(ext = GuardianExt)` lets it pause, and only last until the ledger set by the
+/// admin with `set_guardian_window`. It can never set the admin or upgrade.
+#[contracttrait(
+    default = GuardianRole,
+    extension_required = true,
+    is_extension = true,
+    init_required = true
+)]
+pub trait Guardian {
+    fn guardian(env: &Env) -> Option<soroban_sdk::Address>;
+    /// Replace the guardian, or remove it with `None`. Admin only.
+    fn set_guardian(
+        env: &Env,
+        guardian: Option<soroban_sdk::Address>,
+    ) -> Result<(), crate::AdminError>;
+    /// Last ledger on which the guardian can use its powers, `None` if it has none.
+    fn guardian_live_until_ledger(env: &Env) -> Option<u32>;
+    /// Admin only.
+    fn set_guardian_window(
+        env: &Env,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), crate::AdminError>;
+
+    /// Require that `caller` is the guardian, its window is open, and it authorized the
+    /// invocation.
+    #[internal]
+    fn require_guardian(env: &Env, caller: &soroban_sdk::Address) -> Result<(), crate::AdminError> {
+        if Self::guardian(env).as_ref() != Some(caller) {
+            return Err(crate::AdminError::Unauthorized);
+        }
+        if Self::guardian_live_until_ledger(env)
+            .is_none_or(|ledger| env.ledger().sequence() > ledger)
+        {
+            return Err(crate::AdminError::GuardianWindowClosed);
+        }
+        caller.require_auth();
+        Ok(())
+    }
+
+    #[internal]
+    fn init(
+        env: &Env,
+        guardian: &soroban_sdk::Address,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), crate::AdminError>;
+}
+
+/// Extract the guardian from a contract's constructor arguments.
+pub trait HasGuardian {
+    fn guardian(&self) -> &Address;
+    /// Last ledger the guardian can act on, `None` until the admin opens a window.
+    fn guardian_live_until_ledger(&self) -> Option<u32> {
+        None
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum GuardianKey {
+    Guardian,
+    GuardianWindow,
+}
+
+pub struct GuardianRole<K = AdminSep>(PhantomData<K>);
+
+impl<K: Namespace> Guardian for GuardianRole<K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, GuardianKey::VARIANTS)];
+    fn guardian(env: &Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&storage_key::<K, _>(env, GuardianKey::Guardian))
+    }
+
+    fn set_guardian(env: &Env, guardian: Option<Address>) -> Result<(), AdminError> {
+        let old_guardian = Self::guardian(env);
+        match &guardian {
+            Some(guardian) => env
+                .storage()
+                .instance()
+                .set(&storage_key::<K, _>(env, GuardianKey::Guardian), guardian),
+            None => env
+                .storage()
+                .instance()
+                .remove(&storage_key::<K, _>(env, GuardianKey::Guardian)),
+        }
+        events::guardian_set(env, old_guardian, guardian);
+        Ok(())
+    }
+
+    fn guardian_live_until_ledger(env: &Env) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&storage_key::<K, _>(env, GuardianKey::GuardianWindow))
+    }
+
+    fn set_guardian_window(env: &Env, live_until_ledger: Option<u32>) -> Result<(), AdminError> {
+        match live_until_ledger {
+            Some(ledger) => env.storage().instance().set(
+                &storage_key::<K, _>(env, GuardianKey::GuardianWindow),
+                &ledger,
+            ),
+            None => env
+                .storage()
+                .instance()
+                .remove(&storage_key::<K, _>(env, GuardianKey::GuardianWindow)),
+        }
+        Ok(())
+    }
+
+    fn init(
+        env: &Env,
+        guardian: &Address,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), AdminError> {
+        if Self::guardian(env).is_some() {
+            return Err(AdminError::AlreadyInitialized);
+        }
+        Self::set_guardian(env, Some(guardian.clone()))?;
+        Self::set_guardian_window(env, live_until_ledger)
+    }
+}
+
+impl<T: Administratable, N: Guardian> Guardian for AdministratableExt<T, N> {
+    type Impl = N;
+    fn set_guardian(env: &Env, guardian: Option<Address>) -> Result<(), AdminError> {
+        T::require_admin_for_args(env, Self::set_guardian_auth_args(env, &guardian))?;
+        N::set_guardian(env, guardian)
+    }
+
+    fn set_guardian_window(env: &Env, live_until_ledger: Option<u32>) -> Result<(), AdminError> {
+        T::require_admin_for_args(
+            env,
+            Self::set_guardian_window_auth_args(env, &live_until_ledger),
+        )?;
+        N::set_guardian_window(env, live_until_ledger)
+    }
+}
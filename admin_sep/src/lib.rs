@@ -1,10 +1,37 @@
 #![no_std]
+extern crate self as admin_sep;
+
 pub use contracttrait_macro::*;
 
+mod access_control;
 mod administratable;
 mod constructor;
+mod error;
+pub mod events;
+mod extendable;
+mod guardian;
+mod migratable;
+mod multisig;
+mod pausable;
+mod recoverable;
+mod staged_upgradable;
+mod storage;
+mod timelock;
 mod upgradable;
+mod upgrade_allowlist;
 
+pub use access_control::*;
 pub use administratable::*;
 pub use constructor::*;
+pub use error::*;
+pub use extendable::*;
+pub use guardian::*;
+pub use migratable::*;
+pub use multisig::*;
+pub use pausable::*;
+pub use recoverable::*;
+pub use staged_upgradable::*;
+pub use storage::*;
+pub use timelock::*;
 pub use upgradable::*;
+pub use upgrade_allowlist::*;
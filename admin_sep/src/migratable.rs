@@ -0,0 +1,109 @@
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Namespace, StorageKey, StorageKeys, contracttrait, events, storage_key,
+};
+use soroban_sdk::{Env, contracttype};
+
+use crate::administratable::{Administratable, AdministratableExt};
+use crate::pausable::{Pausable, PausableExt};
+
+/// Trait for migrating a contract's storage after its code is upgraded.
+///
+/// The version of the storage is stored, and each `migrate` moves it up by one version,
+/// so every migration runs exactly once. Wrap `Upgradable` in `MigratableExt` to refuse
+/// upgrading again until the storage has caught up with the code.
+#[contracttrait(
+    default = Migrator,
+    extension_required = true,
+    is_extension = true,
+    init_required = true
+)]
+pub trait Migratable {
+    /// Version of the contract's storage, 0 if it was never set.
+    fn version(env: &Env) -> u32;
+    /// Migrate the storage from `from_version`, its current version, to the next. Admin only.
+    fn migrate(env: &Env, from_version: u32) -> Result<(), crate::AdminError>;
+
+    /// Require that the storage has been migrated to the version of the contract's code.
+    #[internal]
+    fn require_migrated(env: &Env) -> Result<(), crate::AdminError>;
+
+    /// Set the version to that of the contract's code, for a new contract with nothing
+    /// to migrate.
+    #[internal]
+    fn init(env: &Env) -> Result<(), crate::AdminError>;
+}
+
+/// The migrations of a contract, e.g. `Migratable(default = Migrator<Contract>)` with
+/// `impl Migration for Contract`.
+pub trait Migration {
+    /// Version of the storage the contract's code expects.
+    const VERSION: u32;
+    /// Transform the storage from `from_version` to `from_version + 1`.
+    fn migrate(env: &Env, from_version: u32) -> Result<(), AdminError>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum MigratableKey {
+    Version,
+}
+
+pub struct Migrator<M, K = AdminSep>(PhantomData<(M, K)>);
+
+impl<M: Migration, K: Namespace> Migratable for Migrator<M, K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, MigratableKey::VARIANTS)];
+    fn version(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&storage_key::<K, _>(env, MigratableKey::Version))
+            .unwrap_or_default()
+    }
+
+    fn migrate(env: &Env, from_version: u32) -> Result<(), AdminError> {
+        if from_version != Self::version(env) || from_version >= M::VERSION {
+            return Err(AdminError::InvalidMigration);
+        }
+        M::migrate(env, from_version)?;
+        let to_version = from_version + 1;
+        env.storage().instance().set(
+            &storage_key::<K, _>(env, MigratableKey::Version),
+            &to_version,
+        );
+        events::migrated(env, from_version, to_version);
+        Ok(())
+    }
+
+    fn require_migrated(env: &Env) -> Result<(), AdminError> {
+        if Self::version(env) < M::VERSION {
+            return Err(AdminError::NotMigrated);
+        }
+        Ok(())
+    }
+
+    fn init(env: &Env) -> Result<(), AdminError> {
+        env.storage().instance().set(
+            &storage_key::<K, _>(env, MigratableKey::Version),
+            &M::VERSION,
+        );
+        Ok(())
+    }
+}
+
+impl<T: Administratable, N: Migratable> Migratable for AdministratableExt<T, N> {
+    type Impl = N;
+    fn migrate(env: &Env, from_version: u32) -> Result<(), AdminError> {
+        T::require_admin_for_args(env, Self::migrate_auth_args(env, &from_version))?;
+        N::migrate(env, from_version)
+    }
+}
+
+impl<T: Pausable, N: Migratable> Migratable for PausableExt<T, N> {
+    type Impl = N;
+    fn migrate(env: &Env, from_version: u32) -> Result<(), AdminError> {
+        T::require_not_paused(env)?;
+        N::migrate(env, from_version)
+    }
+}
@@ -0,0 +1,242 @@
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Instance, Namespace, StorageKey, StorageKeys, contracttrait, events,
+    storage_key,
+};
+use soroban_sdk::{Address, BytesN, Env, IntoVal, Map, Vec, auth::Context, contracttype, map};
+
+use crate::administratable::{self, Administratable, AdministratableKey};
+
+/// Trait for managing the weighted signers of a `MultisigAdmin`.
+///
+/// The admin of a `MultisigAdmin` contract is the contract itself, so `require_admin`
+/// is checked by the contract's own `__check_auth`. Its `signatures` lists the signers
+/// approving the invocation, whose weights must add up to the threshold. Signers can only
+/// approve calls to the contract's own functions.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't derive `Multisig`",
+    note = "`MultisigAdmin<Contract>` needs the contract's `__check_auth`, compose it with \
+`Multisig`, e.g. derive_contract(Administratable(default = MultisigAdmin<Contract>), Multisig)"
+)]
+#[contracttrait(default = MultisigAdmin)]
+pub trait Multisig {
+    fn signers(env: &Env) -> soroban_sdk::Map<soroban_sdk::Address, u32>;
+    fn threshold(env: &Env) -> u32;
+    /// Add `signer` or change its weight, a weight of 0 removes it. Admin only.
+    fn set_signer(
+        env: &Env,
+        signer: &soroban_sdk::Address,
+        weight: u32,
+    ) -> Result<(), crate::AdminError>;
+    /// Set the total signer weight needed to act as admin. Admin only.
+    fn set_threshold(env: &Env, threshold: u32) -> Result<(), crate::AdminError>;
+    fn __check_auth(
+        env: &Env,
+        signature_payload: soroban_sdk::BytesN<32>,
+        signatures: soroban_sdk::Vec<soroban_sdk::Address>,
+        auth_contexts: soroban_sdk::Vec<soroban_sdk::auth::Context>,
+    ) -> Result<(), crate::AdminError>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum MultisigKey {
+    Config,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigConfig {
+    pub signers: Map<Address, u32>,
+    pub threshold: u32,
+}
+
+impl MultisigConfig {
+    fn single(env: &Env, signer: &Address) -> Self {
+        MultisigConfig {
+            signers: map![env, (signer.clone(), 1)],
+            threshold: 1,
+        }
+    }
+}
+
+fn get<K: Namespace>(env: &Env) -> Result<MultisigConfig, AdminError> {
+    env.storage()
+        .instance()
+        .get(&storage_key::<K, _>(env, MultisigKey::Config))
+        .ok_or(AdminError::NotInitialized)
+}
+
+fn set<K: Namespace>(env: &Env, config: &MultisigConfig) -> Result<(), AdminError> {
+    let total_weight = config
+        .signers
+        .values()
+        .iter()
+        .fold(0u32, u32::saturating_add);
+    if config.threshold == 0 || total_weight < config.threshold {
+        return Err(AdminError::InvalidThreshold);
+    }
+    env.storage()
+        .instance()
+        .set(&storage_key::<K, _>(env, MultisigKey::Config), config);
+    Ok(())
+}
+
+/// `Administratable` for contract `C` where admin rights need the approval of signers whose
+/// combined weight reaches a threshold. `C` must derive `Multisig`, which exports the
+/// `__check_auth` checking them, e.g.
+/// `derive_contract(Administratable(default = MultisigAdmin<Contract>), Multisig)`.
+///
+/// `init`, `set_admin` and `accept_admin` make the given address the only signer,
+/// with a threshold of 1. Their events name the contract as the old admin and the new
+/// signer as the new one.
+pub struct MultisigAdmin<C = (), K = AdminSep>(PhantomData<(C, K)>);
+
+impl<C: Multisig, K: Namespace> Administratable for MultisigAdmin<C, K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[
+        (K::NAMESPACE, MultisigKey::VARIANTS),
+        (K::NAMESPACE, AdministratableKey::VARIANTS),
+    ];
+    fn admin(env: &Env) -> Address {
+        Self::try_admin(env).unwrap_or_else(|e| env.panic_with_error(e))
+    }
+
+    fn try_admin(env: &Env) -> Result<Address, AdminError> {
+        get::<K>(env)?;
+        Ok(env.current_contract_address())
+    }
+
+    fn set_admin(env: &Env, new_admin: &Address) -> Result<(), AdminError> {
+        Self::require_admin_for_args(env, Self::set_admin_auth_args(env, new_admin))?;
+        administratable::remove_pending::<Instance, K>(env);
+        set::<K>(env, &MultisigConfig::single(env, new_admin))?;
+        crate::recoverable::clear(env);
+        events::admin_set(env, &env.current_contract_address(), new_admin);
+        Ok(())
+    }
+
+    fn propose_admin(
+        env: &Env,
+        new_admin: &Address,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), AdminError> {
+        Self::require_admin_for_args(
+            env,
+            Self::propose_admin_auth_args(env, new_admin, &live_until_ledger),
+        )?;
+        Self::nominate_admin(env, new_admin, live_until_ledger)
+    }
+
+    fn accept_admin(env: &Env) -> Result<(), AdminError> {
+        let new_admin = administratable::take_pending::<Instance, K>(env)?;
+        set::<K>(env, &MultisigConfig::single(env, &new_admin))?;
+        crate::recoverable::clear(env);
+        events::admin_set(env, &env.current_contract_address(), &new_admin);
+        Ok(())
+    }
+
+    fn cancel_admin_transfer(env: &Env) -> Result<(), AdminError> {
+        Self::require_admin_for_args(env, Self::cancel_admin_transfer_auth_args(env))?;
+        administratable::remove_pending::<Instance, K>(env);
+        Ok(())
+    }
+
+    fn pending_admin(env: &Env) -> Option<crate::PendingAdmin> {
+        administratable::get_pending::<Instance, K>(env)
+    }
+
+    fn nominate_admin(
+        env: &Env,
+        new_admin: &Address,
+        live_until_ledger: Option<u32>,
+    ) -> Result<(), AdminError> {
+        administratable::set_pending::<Instance, K>(env, new_admin, live_until_ledger);
+        Ok(())
+    }
+
+    fn init(env: &Env, admin: &Address) -> Result<(), AdminError> {
+        if get::<K>(env).is_ok() {
+            return Err(AdminError::AlreadyInitialized);
+        }
+        set::<K>(env, &MultisigConfig::single(env, admin))?;
+        events::admin_initialized(env, &env.current_contract_address());
+        Ok(())
+    }
+}
+
+/// The admin is checked by `MultisigAdmin<Self>`, since `C` is only known to derive `Multisig`
+/// when composed with `Administratable`.
+impl<C, K: Namespace> Multisig for MultisigAdmin<C, K> {
+    type Impl = Self;
+    /// Declared by its `Administratable` impl, which shares its storage.
+    const STORAGE_KEYS: StorageKeys = &[];
+    fn signers(env: &Env) -> Map<Address, u32> {
+        get::<K>(env).map_or_else(|_| Map::new(env), |config| config.signers)
+    }
+
+    fn threshold(env: &Env) -> u32 {
+        get::<K>(env).map_or(0, |config| config.threshold)
+    }
+
+    fn set_signer(env: &Env, signer: &Address, weight: u32) -> Result<(), AdminError> {
+        <MultisigAdmin<Self, K> as Administratable>::require_admin_for_args(
+            env,
+            Self::set_signer_auth_args(env, signer, &weight),
+        )?;
+        let mut config = get::<K>(env)?;
+        if weight == 0 {
+            config.signers.remove(signer.clone());
+        } else {
+            config.signers.set(signer.clone(), weight);
+        }
+        set::<K>(env, &config)
+    }
+
+    fn set_threshold(env: &Env, threshold: u32) -> Result<(), AdminError> {
+        <MultisigAdmin<Self, K> as Administratable>::require_admin_for_args(
+            env,
+            Self::set_threshold_auth_args(env, &threshold),
+        )?;
+        let mut config = get::<K>(env)?;
+        config.threshold = threshold;
+        set::<K>(env, &config)
+    }
+
+    fn __check_auth(
+        env: &Env,
+        signature_payload: BytesN<32>,
+        signatures: Vec<Address>,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), AdminError> {
+        let this = env.current_contract_address();
+        for context in auth_contexts.iter() {
+            match context {
+                Context::Contract(call) if call.contract == this => {}
+                _ => return Err(AdminError::Unauthorized),
+            }
+        }
+        let config = get::<K>(env)?;
+        let mut approved = Map::<Address, ()>::new(env);
+        let mut weight = 0u32;
+        for signer in signatures.iter() {
+            let signer_weight = config
+                .signers
+                .get(signer.clone())
+                .ok_or(AdminError::Unauthorized)?;
+            if approved.contains_key(signer.clone()) {
+                return Err(AdminError::Unauthorized);
+            }
+            approved.set(signer.clone(), ());
+            weight = weight.saturating_add(signer_weight);
+        }
+        if weight < config.threshold {
+            return Err(AdminError::Unauthorized);
+        }
+        for signer in signatures.iter() {
+            signer.require_auth_for_args((signature_payload.clone(),).into_val(env));
+        }
+        Ok(())
+    }
+}
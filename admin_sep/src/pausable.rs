@@ -0,0 +1,122 @@
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Namespace, StorageKey, StorageKeys, contracttrait, events, storage_key,
+};
+use soroban_sdk::{Address, Env, contracttype};
+
+use crate::access_control::{AccessControl, AccessControlExt, PAUSER_ROLE};
+use crate::administratable::{Administratable, AdministratableExt, require_admin_caller};
+use crate::guardian::{Guardian, GuardianExt};
+
+/// Trait for stopping a contract's methods in an emergency.
+///
+/// Methods of other contract traits can be stopped by wrapping them in `PausableExt`,
+/// and a contract's own methods by calling `require_not_paused`. It is implemented for
+/// `Upgradable`, `StagedUpgradable`, `UpgradeAllowlist`, `AccessControl`, `Timelock`,
+/// `Migratable` and `Extendable`, leaving the methods that undo or stop an action, e.g.
+/// `revoke_role` or `cancel`, available during the emergency. The traits that govern the
+/// contract, `Administratable`, `Multisig`, `Guardian` and `Recoverable`, aren't paused.
+///
+/// Pausing is gated by the admin through `AdministratableExt`, holders of `PAUSER_ROLE`
+/// through `AccessControlExt`, or also the guardian through `GuardianExt`.
+#[contracttrait(
+    default = Pauser,
+    extension_required = true,
+    is_extension = true
+)]
+pub trait Pausable {
+    fn paused(env: &Env) -> bool;
+    fn pause(env: &Env, caller: &soroban_sdk::Address) -> Result<(), crate::AdminError>;
+    fn unpause(env: &Env, caller: &soroban_sdk::Address) -> Result<(), crate::AdminError>;
+
+    #[internal]
+    fn require_not_paused(env: &Env) -> Result<(), crate::AdminError> {
+        if Self::paused(env) {
+            return Err(crate::AdminError::Paused);
+        }
+        Ok(())
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum PausableKey {
+    Paused,
+}
+
+pub struct Pauser<K = AdminSep>(PhantomData<K>);
+
+impl<K: Namespace> Pausable for Pauser<K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, PausableKey::VARIANTS)];
+    fn paused(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .has(&storage_key::<K, _>(env, PausableKey::Paused))
+    }
+
+    fn pause(env: &Env, caller: &Address) -> Result<(), AdminError> {
+        Self::require_not_paused(env)?;
+        env.storage()
+            .instance()
+            .set(&storage_key::<K, _>(env, PausableKey::Paused), &());
+        events::paused(env, caller);
+        Ok(())
+    }
+
+    fn unpause(env: &Env, caller: &Address) -> Result<(), AdminError> {
+        if !Self::paused(env) {
+            return Err(AdminError::NotPaused);
+        }
+        env.storage()
+            .instance()
+            .remove(&storage_key::<K, _>(env, PausableKey::Paused));
+        events::unpaused(env, caller);
+        Ok(())
+    }
+}
+
+impl<T: Administratable, N: Pausable> Pausable for AdministratableExt<T, N> {
+    type Impl = N;
+    fn pause(env: &Env, caller: &Address) -> Result<(), AdminError> {
+        require_admin_caller::<T>(env, caller, Self::pause_auth_args(env))?;
+        N::pause(env, caller)
+    }
+
+    fn unpause(env: &Env, caller: &Address) -> Result<(), AdminError> {
+        require_admin_caller::<T>(env, caller, Self::unpause_auth_args(env))?;
+        N::unpause(env, caller)
+    }
+}
+
+impl<T: AccessControl, N: Pausable> Pausable for AccessControlExt<T, N> {
+    type Impl = N;
+    fn pause(env: &Env, caller: &Address) -> Result<(), AdminError> {
+        T::require_role(env, &PAUSER_ROLE, caller)?;
+        N::pause(env, caller)
+    }
+
+    fn unpause(env: &Env, caller: &Address) -> Result<(), AdminError> {
+        T::require_role(env, &PAUSER_ROLE, caller)?;
+        N::unpause(env, caller)
+    }
+}
+
+/// The guardian can pause but not unpause, the admin can do both.
+impl<T: Guardian + Administratable, N: Pausable> Pausable for GuardianExt<T, N> {
+    type Impl = N;
+    fn pause(env: &Env, caller: &Address) -> Result<(), AdminError> {
+        if T::guardian(env).as_ref() == Some(caller) {
+            T::require_guardian(env, caller)?;
+        } else {
+            require_admin_caller::<T>(env, caller, Self::pause_auth_args(env))?;
+        }
+        N::pause(env, caller)
+    }
+
+    fn unpause(env: &Env, caller: &Address) -> Result<(), AdminError> {
+        require_admin_caller::<T>(env, caller, Self::unpause_auth_args(env))?;
+        N::unpause(env, caller)
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{
+    AdminError, AdminSep, Namespace, StorageKey, StorageKeys, contracttrait, events, storage_key,
+};
+use soroban_sdk::{Address, Env, contracttype};
+
+use crate::administratable::{Administratable, AdministratableExt};
+
+/// Trait for recovering admin rights when the admin key is lost.
+///
+/// The admin registers a recovery address. Every call requiring the admin's auth counts as a
+/// heartbeat, as does an explicit `heartbeat`. Once the admin has been inactive for the
+/// configured number of ledgers, the recovery address can `initiate_recovery`, nominating
+/// itself as admin to be accepted with `accept_admin`. An active admin can still turn it down
+/// with `cancel_admin_transfer`. Changing the admin removes the recovery address, so the new
+/// admin has to register its own.
+#[contracttrait(
+    default = Recovery,
+    extension_required = true
+)]
+pub trait Recoverable {
+    fn recovery(env: &Env) -> Option<crate::RecoveryConfig>;
+    /// Register, or remove with `None`, the recovery address. Admin only, counts as a heartbeat.
+    fn set_recovery(
+        env: &Env,
+        recovery: Option<crate::RecoveryConfig>,
+    ) -> Result<(), crate::AdminError>;
+    /// Reset the inactivity clock. Admin only.
+    fn heartbeat(env: &Env) -> Result<(), crate::AdminError>;
+    fn last_heartbeat(env: &Env) -> u32;
+    /// Nominate the recovery address as admin, requires its auth.
+    fn initiate_recovery(env: &Env) -> Result<(), crate::AdminError>;
+}
+
+/// Always in the `AdminSep` namespace, since `require_admin` records the heartbeat.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum RecoverableKey {
+    Recovery,
+    Heartbeat,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryConfig {
+    pub address: Address,
+    /// Number of ledgers without a heartbeat after which recovery can be initiated.
+    pub inactivity_ledgers: u32,
+}
+
+fn get(env: &Env) -> Option<RecoveryConfig> {
+    env.storage()
+        .instance()
+        .get(&storage_key::<AdminSep, _>(env, RecoverableKey::Recovery))
+}
+
+fn set_heartbeat(env: &Env) {
+    env.storage().instance().set(
+        &storage_key::<AdminSep, _>(env, RecoverableKey::Heartbeat),
+        &env.ledger().sequence(),
+    );
+}
+
+/// Record a heartbeat if a recovery address is registered, called whenever the admin's auth
+/// is required.
+pub(crate) fn record_activity(env: &Env) {
+    if get(env).is_some() {
+        set_heartbeat(env);
+    }
+}
+
+/// Remove the recovery address and heartbeat of the previous admin, called whenever the
+/// admin changes.
+pub(crate) fn clear(env: &Env) {
+    let storage = env.storage().instance();
+    storage.remove(&storage_key::<AdminSep, _>(env, RecoverableKey::Recovery));
+    storage.remove(&storage_key::<AdminSep, _>(env, RecoverableKey::Heartbeat));
+}
+
+/// Checks the inactivity of the admin, `AdministratableExt` then nominates the recovery address.
+pub struct Recovery;
+
+impl Recoverable for Recovery {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(AdminSep::NAMESPACE, RecoverableKey::VARIANTS)];
+    fn recovery(env: &Env) -> Option<RecoveryConfig> {
+        get(env)
+    }
+
+    fn set_recovery(env: &Env, recovery: Option<RecoveryConfig>) -> Result<(), AdminError> {
+        match recovery {
+            Some(recovery) => {
+                env.storage().instance().set(
+                    &storage_key::<AdminSep, _>(env, RecoverableKey::Recovery),
+                    &recovery,
+                );
+                set_heartbeat(env);
+            }
+            None => clear(env),
+        }
+        Ok(())
+    }
+
+    fn heartbeat(env: &Env) -> Result<(), AdminError> {
+        set_heartbeat(env);
+        Ok(())
+    }
+
+    fn last_heartbeat(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&storage_key::<AdminSep, _>(env, RecoverableKey::Heartbeat))
+            .unwrap_or_default()
+    }
+
+    fn initiate_recovery(env: &Env) -> Result<(), AdminError> {
+        let recovery = Self::recovery(env).ok_or(AdminError::NoRecovery)?;
+        let recoverable_ledger =
+            Self::last_heartbeat(env).saturating_add(recovery.inactivity_ledgers);
+        if env.ledger().sequence() < recoverable_ledger {
+            return Err(AdminError::AdminActive);
+        }
+        recovery.address.require_auth();
+        events::recovery_initiated(env, &recovery.address);
+        Ok(())
+    }
+}
+
+impl<T: Administratable, N: Recoverable> Recoverable for AdministratableExt<T, N> {
+    type Impl = N;
+    fn set_recovery(env: &Env, recovery: Option<RecoveryConfig>) -> Result<(), AdminError> {
+        T::require_admin_for_args(env, Self::set_recovery_auth_args(env, &recovery))?;
+        N::set_recovery(env, recovery)
+    }
+
+    fn heartbeat(env: &Env) -> Result<(), AdminError> {
+        T::require_admin_for_args(env, Self::heartbeat_auth_args(env))?;
+        N::heartbeat(env)
+    }
+
+    fn initiate_recovery(env: &Env) -> Result<(), AdminError> {
+        N::initiate_recovery(env)?;
+        let recovery = N::recovery(env).ok_or(AdminError::NoRecovery)?;
+        T::nominate_admin(env, &recovery.address, None)
+    }
+}
@@ -0,0 +1,231 @@
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Namespace, StorageKey, StorageKeys, contracttrait, events, storage_key,
+};
+use soroban_sdk::{Address, BytesN, Env, Vec, contracttype};
+
+use crate::access_control::{AccessControl, AccessControlExt, UPGRADER_ROLE};
+use crate::administratable::{Administratable, AdministratableExt, require_admin_caller};
+use crate::pausable::{Pausable, PausableExt};
+use crate::upgradable::{self, Upgradable, UpgradableKey, UpgradeRecord};
+
+/// Trait for upgrading in two steps, proposing a wasm hash and committing it once a delay
+/// has passed.
+///
+/// It has no default: compose it with `StagedUpgrader` as the default of both this trait and
+/// `Upgradable`, e.g. `StagedUpgradable(ext = AdministratableExt, default =
+/// StagedUpgrader<Contract, DELAY>)`. Its extensions gate `propose_upgrade` and
+/// `cancel_upgrade`. `commit_upgrade` calls the contract's own `upgrade`, so the extensions
+/// of `Upgradable` check and authorize it.
+#[contracttrait(extension_required = true)]
+pub trait StagedUpgradable {
+    /// Propose upgrading to `wasm_hash`, replacing any pending proposal. It can be committed
+    /// once the delay of the implementation has passed.
+    fn propose_upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+    /// Upgrade to the pending proposal's wasm hash through the contract's `upgrade`.
+    fn commit_upgrade(
+        env: &soroban_sdk::Env,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+    fn cancel_upgrade(
+        env: &soroban_sdk::Env,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+    fn pending_upgrade(env: &soroban_sdk::Env) -> Option<crate::PendingUpgrade>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum StagedUpgradableKey {
+    PendingUpgrade,
+}
+
+/// A proposed upgrade waiting to be committed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    pub wasm_hash: BytesN<32>,
+    /// First ledger on which the upgrade can be committed.
+    pub ready_ledger: u32,
+}
+
+fn get_pending<K: Namespace>(env: &Env) -> Option<PendingUpgrade> {
+    env.storage().instance().get(&storage_key::<K, _>(
+        env,
+        StagedUpgradableKey::PendingUpgrade,
+    ))
+}
+
+fn propose<K: Namespace>(env: &Env, wasm_hash: BytesN<32>, delay: u32) -> Result<(), AdminError> {
+    if !upgradable::is_upgradable::<K>(env) {
+        return Err(AdminError::UpgradesLocked);
+    }
+    let ready_ledger = env.ledger().sequence().saturating_add(delay);
+    events::upgrade_proposed(env, &wasm_hash, ready_ledger);
+    env.storage().instance().set(
+        &storage_key::<K, _>(env, StagedUpgradableKey::PendingUpgrade),
+        &PendingUpgrade {
+            wasm_hash,
+            ready_ledger,
+        },
+    );
+    Ok(())
+}
+
+/// Remove the pending proposal once it's checked to be for `wasm_hash` and ready.
+fn take_pending<K: Namespace>(env: &Env, wasm_hash: &BytesN<32>) -> Result<(), AdminError> {
+    let pending = get_pending::<K>(env).ok_or(AdminError::NoPendingUpgrade)?;
+    if pending.wasm_hash != *wasm_hash {
+        return Err(AdminError::NoPendingUpgrade);
+    }
+    if env.ledger().sequence() < pending.ready_ledger {
+        return Err(AdminError::UpgradeNotReady);
+    }
+    env.storage().instance().remove(&storage_key::<K, _>(
+        env,
+        StagedUpgradableKey::PendingUpgrade,
+    ));
+    Ok(())
+}
+
+fn cancel<K: Namespace>(env: &Env) -> Result<(), AdminError> {
+    let pending = get_pending::<K>(env).ok_or(AdminError::NoPendingUpgrade)?;
+    env.storage().instance().remove(&storage_key::<K, _>(
+        env,
+        StagedUpgradableKey::PendingUpgrade,
+    ));
+    events::upgrade_cancelled(env, &pending.wasm_hash);
+    Ok(())
+}
+
+/// An `Upgradable` and `StagedUpgradable` for contract `C` that only upgrades to a wasm hash
+/// proposed at least `DELAY` ledgers before, e.g. `StagedUpgrader<Contract, { 7 *
+/// DAY_IN_LEDGERS }>`, giving users time to review the new code or exit. `rollback` is
+/// immediate, since it reinstalls code that was already live.
+pub struct StagedUpgrader<C, const DELAY: u32, K = AdminSep>(PhantomData<(C, K)>);
+
+impl<C, const DELAY: u32, K: Namespace> Upgradable for StagedUpgrader<C, DELAY, K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, UpgradableKey::VARIANTS)];
+    /// Like `commit_upgrade`, but only if the pending proposal is for `wasm_hash`.
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), AdminError> {
+        take_pending::<K>(env, &wasm_hash)?;
+        upgradable::upgrade::<K>(env, wasm_hash, operator)
+    }
+
+    fn current_wasm_hash(env: &Env) -> Option<BytesN<32>> {
+        upgradable::current_wasm_hash::<K>(env)
+    }
+
+    fn upgrade_history(env: &Env, start: u32, limit: u32) -> Vec<UpgradeRecord> {
+        upgradable::upgrade_history::<K>(env, start, limit)
+    }
+
+    fn lock_upgrades(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        upgradable::lock::<K>(env, operator)
+    }
+
+    fn is_upgradable(env: &Env) -> bool {
+        upgradable::is_upgradable::<K>(env)
+    }
+
+    fn rollback(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        upgradable::rollback::<K>(env, operator)
+    }
+
+    fn rollback_target(env: &Env) -> Option<BytesN<32>> {
+        upgradable::rollback_targets::<K>(env).last()
+    }
+
+    fn init_wasm_hash(env: &Env, wasm_hash: &BytesN<32>) -> Result<(), AdminError> {
+        upgradable::init_wasm_hash::<K>(env, wasm_hash)
+    }
+}
+
+impl<C: Upgradable, const DELAY: u32, K: Namespace> StagedUpgradable
+    for StagedUpgrader<C, DELAY, K>
+{
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, StagedUpgradableKey::VARIANTS)];
+    fn propose_upgrade(
+        env: &Env,
+        wasm_hash: BytesN<32>,
+        _operator: &Address,
+    ) -> Result<(), AdminError> {
+        propose::<K>(env, wasm_hash, DELAY)
+    }
+
+    fn commit_upgrade(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        let pending = get_pending::<K>(env).ok_or(AdminError::NoPendingUpgrade)?;
+        C::upgrade(env, pending.wasm_hash, operator)
+    }
+
+    fn cancel_upgrade(env: &Env, _operator: &Address) -> Result<(), AdminError> {
+        cancel::<K>(env)
+    }
+
+    fn pending_upgrade(env: &Env) -> Option<PendingUpgrade> {
+        get_pending::<K>(env)
+    }
+}
+
+impl<T: Administratable, N: StagedUpgradable> StagedUpgradable for AdministratableExt<T, N> {
+    type Impl = N;
+    fn propose_upgrade(
+        env: &Env,
+        wasm_hash: BytesN<32>,
+        operator: &Address,
+    ) -> Result<(), AdminError> {
+        require_admin_caller::<T>(
+            env,
+            operator,
+            Self::propose_upgrade_auth_args(env, &wasm_hash),
+        )?;
+        N::propose_upgrade(env, wasm_hash, operator)
+    }
+
+    fn cancel_upgrade(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        require_admin_caller::<T>(env, operator, Self::cancel_upgrade_auth_args(env))?;
+        N::cancel_upgrade(env, operator)
+    }
+}
+
+impl<T: AccessControl, N: StagedUpgradable> StagedUpgradable for AccessControlExt<T, N> {
+    type Impl = N;
+    fn propose_upgrade(
+        env: &Env,
+        wasm_hash: BytesN<32>,
+        operator: &Address,
+    ) -> Result<(), AdminError> {
+        T::require_role(env, &UPGRADER_ROLE, operator)?;
+        N::propose_upgrade(env, wasm_hash, operator)
+    }
+
+    fn cancel_upgrade(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        T::require_role(env, &UPGRADER_ROLE, operator)?;
+        N::cancel_upgrade(env, operator)
+    }
+}
+
+/// A pending upgrade can still be cancelled while paused. Committing it calls `upgrade`, so it
+/// is stopped by wrapping `Upgradable` in `PausableExt`.
+impl<T: Pausable, N: StagedUpgradable> StagedUpgradable for PausableExt<T, N> {
+    type Impl = N;
+    fn propose_upgrade(
+        env: &Env,
+        wasm_hash: BytesN<32>,
+        operator: &Address,
+    ) -> Result<(), AdminError> {
+        T::require_not_paused(env)?;
+        N::propose_upgrade(env, wasm_hash, operator)
+    }
+}
@@ -0,0 +1,89 @@
+use soroban_sdk::{Env, IntoVal, Symbol, TryFromVal, Val};
+
+/// Namespace of the storage keys of a default implementation.
+///
+/// Each contract trait has a `#[contracttype]` key enum, e.g. `AdministratableKey`, and its
+/// default implementation stores `(NAMESPACE, key)`. A `#[contracttype]` enum is encoded by
+/// its variant name alone, not its type, so two enums with a variant of the same name clash
+/// in the same namespace. Default implementations take their namespace as a type parameter,
+/// so it can be overridden with e.g. `Pausable(default = Pauser<MyNamespace>)`.
+///
+/// Implementations declare their key enums with their namespace as `STORAGE_KEYS`, which
+/// `derive_contract` checks against each other and the contract's own `storage_keys`.
+pub trait Namespace {
+    const NAMESPACE: &'static str;
+}
+
+/// A storage key enum, listing the variant names it encodes to. Derived with
+/// `#[derive(StorageKey)]`.
+pub trait StorageKey {
+    const VARIANTS: &'static [&'static str];
+}
+
+/// The storage keys of an implementation, as `(namespace, variants)` pairs.
+pub type StorageKeys = &'static [(&'static str, &'static [&'static str])];
+
+/// The namespace used by default, `"admin_sep"`.
+pub struct AdminSep;
+
+impl Namespace for AdminSep {
+    const NAMESPACE: &'static str = "admin_sep";
+}
+
+/// Storage tier a default implementation keeps its entries in, e.g. `Admin<Persistent>`.
+pub trait Tier {
+    fn get<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>>(env: &Env, key: &K) -> Option<V>;
+    fn set<K: IntoVal<Env, Val>, V: IntoVal<Env, Val>>(env: &Env, key: &K, value: &V);
+    fn remove<K: IntoVal<Env, Val>>(env: &Env, key: &K);
+}
+
+/// Instance storage: loaded with every invocation and live as long as the instance is.
+pub struct Instance;
+
+impl Tier for Instance {
+    fn get<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>>(env: &Env, key: &K) -> Option<V> {
+        env.storage().instance().get(key)
+    }
+
+    fn set<K: IntoVal<Env, Val>, V: IntoVal<Env, Val>>(env: &Env, key: &K, value: &V) {
+        env.storage().instance().set(key, value);
+    }
+
+    fn remove<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        env.storage().instance().remove(key);
+    }
+}
+
+/// Persistent storage: only loaded when accessed. Entries have their own TTL, which is
+/// extended according to the `TtlConfig` whenever they are read or written.
+pub struct Persistent;
+
+impl Tier for Persistent {
+    fn get<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>>(env: &Env, key: &K) -> Option<V> {
+        let value = env.storage().persistent().get(key);
+        if value.is_some() {
+            let (threshold, extend_to) = crate::extendable::ttl_extension(env);
+            env.storage()
+                .persistent()
+                .extend_ttl(key, threshold, extend_to);
+        }
+        value
+    }
+
+    fn set<K: IntoVal<Env, Val>, V: IntoVal<Env, Val>>(env: &Env, key: &K, value: &V) {
+        env.storage().persistent().set(key, value);
+        let (threshold, extend_to) = crate::extendable::ttl_extension(env);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, threshold, extend_to);
+    }
+
+    fn remove<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        env.storage().persistent().remove(key);
+    }
+}
+
+/// The storage key of `key` in namespace `K`.
+pub fn storage_key<K: Namespace, T>(env: &Env, key: T) -> (Symbol, T) {
+    (Symbol::new(env, K::NAMESPACE), key)
+}
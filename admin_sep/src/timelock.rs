@@ -0,0 +1,187 @@
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Namespace, Persistent, StorageKey, StorageKeys, Tier, contracttrait,
+    events, storage_key,
+};
+use soroban_sdk::{BytesN, Env, Symbol, Val, Vec, contracttype, xdr::ToXdr};
+
+use crate::administratable::{Administratable, AdministratableExt};
+use crate::pausable::{Pausable, PausableExt};
+
+/// Trait for delaying operations, so users get a guaranteed window to react before they run.
+///
+/// An operation is a call of `fn_name` with `args`, identified by `operation_id`. Once
+/// scheduled it can be executed after its delay, e.g. by `TimelockExt` when the wrapped
+/// method is called with the same arguments. Operations are kept in persistent storage, so any
+/// number of them can be pending.
+#[contracttrait(
+    default = TimelockController,
+    extension_required = true,
+    is_extension = true
+)]
+pub trait Timelock {
+    /// Minimum number of ledgers between scheduling and executing an operation.
+    fn min_delay(env: &Env) -> u32;
+    /// Once a minimum delay is set, changing it must itself be scheduled as
+    /// `set_min_delay` with `args` of `[delay]`.
+    fn set_min_delay(env: &Env, delay: u32) -> Result<(), crate::AdminError>;
+    /// Schedule calling `fn_name` with `args` in `delay` ledgers, returning the operation's id.
+    fn schedule(
+        env: &Env,
+        fn_name: &soroban_sdk::Symbol,
+        args: &soroban_sdk::Vec<soroban_sdk::Val>,
+        delay: u32,
+    ) -> Result<soroban_sdk::BytesN<32>, crate::AdminError>;
+    fn cancel(env: &Env, id: &soroban_sdk::BytesN<32>) -> Result<(), crate::AdminError>;
+    fn get_operation(env: &Env, id: &soroban_sdk::BytesN<32>) -> Option<crate::Operation>;
+
+    /// Consume the operation `id`, failing unless it is scheduled and its delay has passed.
+    ///
+    /// Not exported: an operation is executed by calling the method it schedules, which
+    /// consumes it on the way. Exported, anyone could consume a ready operation without
+    /// running it.
+    #[internal]
+    fn execute(env: &Env, id: &soroban_sdk::BytesN<32>) -> Result<(), crate::AdminError>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum TimelockKey {
+    MinDelay,
+    /// A scheduled operation, in persistent storage.
+    Operation(BytesN<32>),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Operation {
+    /// First ledger on which the operation can be executed.
+    pub ready_ledger: u32,
+}
+
+/// The id of calling `fn_name` with `args`.
+pub fn operation_id(env: &Env, fn_name: &Symbol, args: &Vec<Val>) -> BytesN<32> {
+    env.crypto()
+        .sha256(&(fn_name.clone(), args.clone()).to_xdr(env))
+        .into()
+}
+
+fn operation_key<K: Namespace>(env: &Env, id: &BytesN<32>) -> (Symbol, TimelockKey) {
+    storage_key::<K, _>(env, TimelockKey::Operation(id.clone()))
+}
+
+pub struct TimelockController<K = AdminSep>(PhantomData<K>);
+
+impl<K: Namespace> Timelock for TimelockController<K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, TimelockKey::VARIANTS)];
+    fn min_delay(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&storage_key::<K, _>(env, TimelockKey::MinDelay))
+            .unwrap_or_default()
+    }
+
+    fn set_min_delay(env: &Env, delay: u32) -> Result<(), AdminError> {
+        if Self::min_delay(env) > 0 {
+            let args = Self::set_min_delay_auth_args(env, &delay);
+            Self::execute(
+                env,
+                &operation_id(env, &Symbol::new(env, "set_min_delay"), &args),
+            )?;
+        }
+        env.storage()
+            .instance()
+            .set(&storage_key::<K, _>(env, TimelockKey::MinDelay), &delay);
+        Ok(())
+    }
+
+    fn schedule(
+        env: &Env,
+        fn_name: &Symbol,
+        args: &Vec<Val>,
+        delay: u32,
+    ) -> Result<BytesN<32>, AdminError> {
+        if delay < Self::min_delay(env) {
+            return Err(AdminError::DelayTooShort);
+        }
+        let id = operation_id(env, fn_name, args);
+        if Self::get_operation(env, &id).is_some() {
+            return Err(AdminError::OperationAlreadyScheduled);
+        }
+        let ready_ledger = env.ledger().sequence().saturating_add(delay);
+        Persistent::set(
+            env,
+            &operation_key::<K>(env, &id),
+            &Operation { ready_ledger },
+        );
+        events::operation_scheduled(env, &id, fn_name, args, ready_ledger);
+        Ok(id)
+    }
+
+    fn cancel(env: &Env, id: &BytesN<32>) -> Result<(), AdminError> {
+        if Self::get_operation(env, id).is_none() {
+            return Err(AdminError::OperationNotScheduled);
+        }
+        Persistent::remove(env, &operation_key::<K>(env, id));
+        events::operation_cancelled(env, id);
+        Ok(())
+    }
+
+    fn get_operation(env: &Env, id: &BytesN<32>) -> Option<Operation> {
+        Persistent::get(env, &operation_key::<K>(env, id))
+    }
+
+    fn execute(env: &Env, id: &BytesN<32>) -> Result<(), AdminError> {
+        let operation = Self::get_operation(env, id).ok_or(AdminError::OperationNotScheduled)?;
+        if env.ledger().sequence() < operation.ready_ledger {
+            return Err(AdminError::OperationNotReady);
+        }
+        Persistent::remove(env, &operation_key::<K>(env, id));
+        events::operation_executed(env, id);
+        Ok(())
+    }
+}
+
+impl<T: Administratable, N: Timelock> Timelock for AdministratableExt<T, N> {
+    type Impl = N;
+    fn set_min_delay(env: &Env, delay: u32) -> Result<(), AdminError> {
+        T::require_admin_for_args(env, Self::set_min_delay_auth_args(env, &delay))?;
+        N::set_min_delay(env, delay)
+    }
+
+    fn schedule(
+        env: &Env,
+        fn_name: &Symbol,
+        args: &Vec<Val>,
+        delay: u32,
+    ) -> Result<BytesN<32>, AdminError> {
+        T::require_admin_for_args(env, Self::schedule_auth_args(env, fn_name, args, &delay))?;
+        N::schedule(env, fn_name, args, delay)
+    }
+
+    fn cancel(env: &Env, id: &BytesN<32>) -> Result<(), AdminError> {
+        T::require_admin_for_args(env, Self::cancel_auth_args(env, id))?;
+        N::cancel(env, id)
+    }
+}
+
+/// Operations can still be cancelled while paused, so a pending one can be stopped.
+impl<T: Pausable, N: Timelock> Timelock for PausableExt<T, N> {
+    type Impl = N;
+    fn set_min_delay(env: &Env, delay: u32) -> Result<(), AdminError> {
+        T::require_not_paused(env)?;
+        N::set_min_delay(env, delay)
+    }
+
+    fn schedule(
+        env: &Env,
+        fn_name: &Symbol,
+        args: &Vec<Val>,
+        delay: u32,
+    ) -> Result<BytesN<32>, AdminError> {
+        T::require_not_paused(env)?;
+        N::schedule(env, fn_name, args, delay)
+    }
+}
@@ -1,25 +1,448 @@
-use crate::contracttrait;
+use core::marker::PhantomData;
 
-use crate::administratable::{Administratable, AdministratableExt};
+use crate::{
+    AdminError, AdminSep, Namespace, Persistent, StorageKey, StorageKeys, Tier, contracttrait,
+    events, storage_key,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Symbol, Vec, contracttype, symbol_short};
 
-#[contracttrait(default = Upgrader, extension_required = true)]
+use crate::access_control::{AccessControl, AccessControlExt, UPGRADER_ROLE};
+use crate::administratable::{Administratable, AdministratableExt, require_admin_caller};
+use crate::migratable::{Migratable, MigratableExt};
+use crate::pausable::{Pausable, PausableExt};
+use crate::timelock::{Timelock, TimelockExt, operation_id};
+use crate::upgrade_allowlist::{UpgradeAllowlist, UpgradeAllowlistExt};
+
+/// Trait for replacing a contract's code.
+///
+/// There is no `upgrade_and_call`: the new wasm is only installed once the invocation of
+/// `upgrade` has finished, and a contract can't invoke itself, so a call made from `upgrade`
+/// would still run the old code. To migrate in the same transaction, call `upgrade` and then
+/// e.g. `Migratable::migrate` from another contract, and have the contract's methods
+/// `require_migrated` so it can't be used half-migrated.
+///
+/// To propose upgrades and commit them after a delay, compose `StagedUpgradable` too.
+///
+/// A contract can't learn its own wasm hash, so until the first `upgrade` there is no hash to
+/// roll back to or to compare against. Record the hash it is deployed with by wrapping
+/// `Constructable` in `UpgradableExt`, with arguments implementing `HasWasmHash`.
+#[contracttrait(
+    default = Upgrader,
+    extension_required = true,
+    is_extension = true
+)]
 pub trait Upgradable {
-    fn upgrade(env: &soroban_sdk::Env, wasm_hash: soroban_sdk::BytesN<32>);
+    /// Replace the contract's wasm with `wasm_hash`. `operator` is the address authorizing
+    /// the upgrade: a holder of `UPGRADER_ROLE` through `AccessControlExt`, which can't know
+    /// who to check otherwise, and the admin itself through `AdministratableExt`. It is kept
+    /// in the `UpgradeRecord`.
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+    /// The wasm hash installed by the last `upgrade` or recorded at construction, `None` if
+    /// there is neither.
+    fn current_wasm_hash(env: &soroban_sdk::Env) -> Option<soroban_sdk::BytesN<32>>;
+    /// Up to `limit` of the recorded upgrades, oldest first, starting from the `start`th.
+    fn upgrade_history(
+        env: &soroban_sdk::Env,
+        start: u32,
+        limit: u32,
+    ) -> soroban_sdk::Vec<crate::UpgradeRecord>;
+
+    /// Permanently disable upgrading, so the contract's code can never change again.
+    fn lock_upgrades(
+        env: &soroban_sdk::Env,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+    /// `false` once `lock_upgrades` was called.
+    fn is_upgradable(env: &soroban_sdk::Env) -> bool;
+
+    /// Reinstall the wasm hash replaced by the last `upgrade` that wasn't rolled back yet,
+    /// going back up to `MAX_ROLLBACKS` upgrades.
+    fn rollback(
+        env: &soroban_sdk::Env,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), crate::AdminError>;
+    /// The wasm hash `rollback` would reinstall, `None` if there is none.
+    fn rollback_target(env: &soroban_sdk::Env) -> Option<soroban_sdk::BytesN<32>>;
+
+    #[internal]
+    fn require_upgradable(env: &soroban_sdk::Env) -> Result<(), crate::AdminError> {
+        if !Self::is_upgradable(env) {
+            return Err(crate::AdminError::UpgradesLocked);
+        }
+        Ok(())
+    }
+
+    /// Record `wasm_hash` as the contract's current code, without installing it.
+    #[internal]
+    fn init_wasm_hash(
+        env: &soroban_sdk::Env,
+        wasm_hash: &soroban_sdk::BytesN<32>,
+    ) -> Result<(), crate::AdminError>;
+}
+
+/// Extract the wasm hash a contract is deployed with from its constructor arguments. It is
+/// trusted as given, since the contract has no way to check it.
+pub trait HasWasmHash {
+    fn wasm_hash(&self) -> &BytesN<32>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum UpgradableKey {
+    /// The wasm hash installed by the last `upgrade` or recorded at construction.
+    WasmHash,
+    UpgradeCount,
+    /// The `UpgradeRecord` of the nth upgrade, in persistent storage.
+    Upgrade(u32),
+    UpgradesLocked,
+    /// The wasm hashes replaced by upgrades, most recent last.
+    RollbackTargets,
+}
+
+/// Number of previous wasm hashes kept for `rollback`.
+pub const MAX_ROLLBACKS: u32 = 5;
+
+/// An `upgrade`, kept on-chain as an audit trail of the contract's code.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeRecord {
+    /// `None` if the contract was not upgraded through `Upgradable` before, since a contract
+    /// cannot otherwise learn its own wasm hash. Encoded like the `Option<BytesN<32>>` of the
+    /// `upgraded` event; it is `Bytes` because `#[contracttype]` can't hold an optional
+    /// `BytesN` yet.
+    pub old_wasm_hash: Option<Bytes>,
+    pub new_wasm_hash: BytesN<32>,
+    /// Ledger sequence of the upgrade.
+    pub ledger: u32,
+    /// The address that authorized the upgrade.
+    pub operator: Address,
+}
+
+fn upgrade_count<K: Namespace>(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&storage_key::<K, _>(env, UpgradableKey::UpgradeCount))
+        .unwrap_or_default()
+}
+
+fn record_upgrade<K: Namespace>(env: &Env, record: &UpgradeRecord) {
+    let count = upgrade_count::<K>(env);
+    Persistent::set(
+        env,
+        &storage_key::<K, _>(env, UpgradableKey::Upgrade(count)),
+        record,
+    );
+    env.storage().instance().set(
+        &storage_key::<K, _>(env, UpgradableKey::UpgradeCount),
+        &(count + 1),
+    );
+}
+
+pub(crate) fn current_wasm_hash<K: Namespace>(env: &Env) -> Option<BytesN<32>> {
+    env.storage()
+        .instance()
+        .get(&storage_key::<K, _>(env, UpgradableKey::WasmHash))
+}
+
+pub(crate) fn init_wasm_hash<K: Namespace>(
+    env: &Env,
+    wasm_hash: &BytesN<32>,
+) -> Result<(), AdminError> {
+    let key = storage_key::<K, _>(env, UpgradableKey::WasmHash);
+    if env.storage().instance().has(&key) {
+        return Err(AdminError::AlreadyInitialized);
+    }
+    env.storage().instance().set(&key, wasm_hash);
+    Ok(())
 }
 
-pub struct Upgrader;
+pub(crate) fn is_upgradable<K: Namespace>(env: &Env) -> bool {
+    !env.storage()
+        .instance()
+        .has(&storage_key::<K, _>(env, UpgradableKey::UpgradesLocked))
+}
 
-impl Upgradable for Upgrader {
-    type Impl = Upgrader;
-    fn upgrade(env: &soroban_sdk::Env, wasm_hash: soroban_sdk::BytesN<32>) {
-        env.deployer().update_current_contract_wasm(wasm_hash);
+pub(crate) fn lock<K: Namespace>(env: &Env, operator: &Address) -> Result<(), AdminError> {
+    if !is_upgradable::<K>(env) {
+        return Err(AdminError::UpgradesLocked);
+    }
+    env.storage().instance().set(
+        &storage_key::<K, _>(env, UpgradableKey::UpgradesLocked),
+        &(),
+    );
+    events::upgrades_locked(env, operator);
+    Ok(())
+}
+
+fn install<K: Namespace>(
+    env: &Env,
+    wasm_hash: BytesN<32>,
+    operator: &Address,
+) -> Result<(), AdminError> {
+    if !is_upgradable::<K>(env) {
+        return Err(AdminError::UpgradesLocked);
+    }
+    let old_wasm_hash = current_wasm_hash::<K>(env);
+    env.deployer()
+        .update_current_contract_wasm(wasm_hash.clone());
+    env.storage().instance().set(
+        &storage_key::<K, _>(env, UpgradableKey::WasmHash),
+        &wasm_hash,
+    );
+    record_upgrade::<K>(
+        env,
+        &UpgradeRecord {
+            old_wasm_hash: old_wasm_hash.clone().map(Into::into),
+            new_wasm_hash: wasm_hash.clone(),
+            ledger: env.ledger().sequence(),
+            operator: operator.clone(),
+        },
+    );
+    events::upgraded(env, old_wasm_hash, &wasm_hash);
+    Ok(())
+}
+
+pub(crate) fn rollback_targets<K: Namespace>(env: &Env) -> Vec<BytesN<32>> {
+    env.storage()
+        .instance()
+        .get(&storage_key::<K, _>(env, UpgradableKey::RollbackTargets))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_rollback_targets<K: Namespace>(env: &Env, targets: &Vec<BytesN<32>>) {
+    env.storage().instance().set(
+        &storage_key::<K, _>(env, UpgradableKey::RollbackTargets),
+        targets,
+    );
+}
+
+/// Install `wasm_hash`, keeping the replaced hash to roll back to.
+pub(crate) fn upgrade<K: Namespace>(
+    env: &Env,
+    wasm_hash: BytesN<32>,
+    operator: &Address,
+) -> Result<(), AdminError> {
+    let old_wasm_hash = current_wasm_hash::<K>(env);
+    install::<K>(env, wasm_hash, operator)?;
+    if let Some(old_wasm_hash) = old_wasm_hash {
+        let mut targets = rollback_targets::<K>(env);
+        targets.push_back(old_wasm_hash);
+        if targets.len() > MAX_ROLLBACKS {
+            targets.pop_front();
+        }
+        set_rollback_targets::<K>(env, &targets);
+    }
+    Ok(())
+}
+
+pub(crate) fn rollback<K: Namespace>(env: &Env, operator: &Address) -> Result<(), AdminError> {
+    let mut targets = rollback_targets::<K>(env);
+    let wasm_hash = targets.pop_back().ok_or(AdminError::NoRollbackTarget)?;
+    install::<K>(env, wasm_hash, operator)?;
+    set_rollback_targets::<K>(env, &targets);
+    Ok(())
+}
+
+pub(crate) fn upgrade_history<K: Namespace>(
+    env: &Env,
+    start: u32,
+    limit: u32,
+) -> Vec<UpgradeRecord> {
+    let end = upgrade_count::<K>(env).min(start.saturating_add(limit));
+    let mut history = Vec::new(env);
+    for i in start..end {
+        if let Some(record) =
+            Persistent::get(env, &storage_key::<K, _>(env, UpgradableKey::Upgrade(i)))
+        {
+            history.push_back(record);
+        }
+    }
+    history
+}
+
+/// The default `Upgradable`, upgrading as soon as `upgrade` is called.
+pub struct Upgrader<K = AdminSep>(PhantomData<K>);
+
+impl<K: Namespace> Upgradable for Upgrader<K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, UpgradableKey::VARIANTS)];
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), AdminError> {
+        upgrade::<K>(env, wasm_hash, operator)
+    }
+
+    fn current_wasm_hash(env: &Env) -> Option<BytesN<32>> {
+        current_wasm_hash::<K>(env)
+    }
+
+    fn upgrade_history(env: &Env, start: u32, limit: u32) -> Vec<UpgradeRecord> {
+        upgrade_history::<K>(env, start, limit)
+    }
+
+    fn lock_upgrades(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        lock::<K>(env, operator)
+    }
+
+    fn is_upgradable(env: &Env) -> bool {
+        is_upgradable::<K>(env)
+    }
+
+    fn rollback(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        rollback::<K>(env, operator)
+    }
+
+    fn rollback_target(env: &Env) -> Option<BytesN<32>> {
+        rollback_targets::<K>(env).last()
+    }
+
+    fn init_wasm_hash(env: &Env, wasm_hash: &BytesN<32>) -> Result<(), AdminError> {
+        init_wasm_hash::<K>(env, wasm_hash)
     }
 }
 
 impl<T: Administratable, N: Upgradable> Upgradable for AdministratableExt<T, N> {
     type Impl = N;
-    fn upgrade(env: &soroban_sdk::Env, wasm_hash: soroban_sdk::BytesN<32>) {
-        T::require_admin(env);
-        N::upgrade(env, wasm_hash);
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), AdminError> {
+        N::require_upgradable(env)?;
+        require_admin_caller::<T>(env, operator, Self::upgrade_auth_args(env, &wasm_hash))?;
+        N::upgrade(env, wasm_hash, operator)
+    }
+
+    fn lock_upgrades(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        require_admin_caller::<T>(env, operator, Self::lock_upgrades_auth_args(env))?;
+        N::lock_upgrades(env, operator)
+    }
+
+    fn rollback(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        N::require_upgradable(env)?;
+        require_admin_caller::<T>(env, operator, Self::rollback_auth_args(env))?;
+        N::rollback(env, operator)
+    }
+}
+
+impl<T: AccessControl, N: Upgradable> Upgradable for AccessControlExt<T, N> {
+    type Impl = N;
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), AdminError> {
+        T::require_role(env, &UPGRADER_ROLE, operator)?;
+        N::upgrade(env, wasm_hash, operator)
+    }
+
+    fn lock_upgrades(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        T::require_role(env, &UPGRADER_ROLE, operator)?;
+        N::lock_upgrades(env, operator)
+    }
+
+    fn rollback(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        T::require_role(env, &UPGRADER_ROLE, operator)?;
+        N::rollback(env, operator)
+    }
+}
+
+/// Operations are identified by the method and its arguments other than `operator`, e.g.
+/// `upgrade` with `[wasm_hash]`: whoever is allowed to upgrade may execute an upgrade
+/// scheduled by another operator.
+impl<T: Timelock, N: Upgradable> Upgradable for TimelockExt<T, N> {
+    type Impl = N;
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), AdminError> {
+        let args = Self::upgrade_auth_args(env, &wasm_hash);
+        T::execute(env, &operation_id(env, &symbol_short!("upgrade"), &args))?;
+        N::upgrade(env, wasm_hash, operator)
+    }
+
+    fn lock_upgrades(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        let id = operation_id(
+            env,
+            &Symbol::new(env, "lock_upgrades"),
+            &Self::lock_upgrades_auth_args(env),
+        );
+        T::execute(env, &id)?;
+        N::lock_upgrades(env, operator)
+    }
+
+    fn rollback(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        T::execute(
+            env,
+            &operation_id(
+                env,
+                &symbol_short!("rollback"),
+                &Self::rollback_auth_args(env),
+            ),
+        )?;
+        N::rollback(env, operator)
+    }
+}
+
+/// `rollback` is still allowed while paused, as a way out of the emergency.
+impl<T: Pausable, N: Upgradable> Upgradable for PausableExt<T, N> {
+    type Impl = N;
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), AdminError> {
+        T::require_not_paused(env)?;
+        N::upgrade(env, wasm_hash, operator)
+    }
+}
+
+/// Upgrade only once the storage is migrated to the current code, so no migration is skipped.
+impl<T: Migratable, N: Upgradable> Upgradable for MigratableExt<T, N> {
+    type Impl = N;
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), AdminError> {
+        T::require_migrated(env)?;
+        N::upgrade(env, wasm_hash, operator)
+    }
+}
+
+/// Require that `wasm_hash` is approved and not already installed.
+fn require_upgrade_to<T: UpgradeAllowlist, N: Upgradable>(
+    env: &Env,
+    wasm_hash: &BytesN<32>,
+) -> Result<(), AdminError> {
+    if N::current_wasm_hash(env).as_ref() == Some(wasm_hash) {
+        return Err(AdminError::WasmAlreadyInstalled);
+    }
+    T::require_approved(env, wasm_hash)
+}
+
+impl<T: UpgradeAllowlist, N: Upgradable> Upgradable for UpgradeAllowlistExt<T, N> {
+    type Impl = N;
+    fn upgrade(
+        env: &soroban_sdk::Env,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        operator: &soroban_sdk::Address,
+    ) -> Result<(), AdminError> {
+        require_upgrade_to::<T, N>(env, &wasm_hash)?;
+        N::upgrade(env, wasm_hash, operator)
+    }
+
+    /// A revoked hash can't be rolled back to either.
+    fn rollback(env: &Env, operator: &Address) -> Result<(), AdminError> {
+        let wasm_hash = N::rollback_target(env).ok_or(AdminError::NoRollbackTarget)?;
+        T::require_approved(env, &wasm_hash)?;
+        N::rollback(env, operator)
     }
 }
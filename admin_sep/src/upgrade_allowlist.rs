@@ -0,0 +1,152 @@
+use core::marker::PhantomData;
+
+use crate::{
+    AdminError, AdminSep, Namespace, Persistent, StorageKey, StorageKeys, Tier, contracttrait,
+    events, storage_key,
+};
+use soroban_sdk::{Address, BytesN, Env, contracttype};
+
+use crate::access_control::{APPROVER_ROLE, AccessControl, AccessControlExt};
+use crate::administratable::{Administratable, AdministratableExt, require_admin_caller};
+use crate::pausable::{Pausable, PausableExt};
+
+/// Trait for approving the wasm hashes a contract may be upgraded to.
+///
+/// Wrap `Upgradable` in `UpgradeAllowlistExt` to reject upgrading to a hash that is not
+/// approved, or that is already installed. The installed hash is only known after the first
+/// `upgrade`, unless it was recorded at construction through `UpgradableExt`. Approved hashes
+/// are kept in persistent storage, so any number of them can be approved.
+///
+/// Approving with a different key than upgrading, e.g. through `AccessControlExt`, means a
+/// single compromised key can't push arbitrary code.
+#[contracttrait(
+    default = ApprovedWasms,
+    extension_required = true,
+    is_extension = true
+)]
+pub trait UpgradeAllowlist {
+    fn is_wasm_approved(env: &Env, wasm_hash: &soroban_sdk::BytesN<32>) -> bool;
+    /// Allow upgrading to `wasm_hash`. `approver` is the address authorizing it, e.g. the
+    /// admin or a holder of `APPROVER_ROLE`.
+    fn approve_wasm(
+        env: &Env,
+        approver: &soroban_sdk::Address,
+        wasm_hash: &soroban_sdk::BytesN<32>,
+    ) -> Result<(), crate::AdminError>;
+    /// Stop allowing upgrading to `wasm_hash`, including an upgrade already proposed.
+    fn revoke_wasm(
+        env: &Env,
+        approver: &soroban_sdk::Address,
+        wasm_hash: &soroban_sdk::BytesN<32>,
+    ) -> Result<(), crate::AdminError>;
+
+    #[internal]
+    fn require_approved(
+        env: &Env,
+        wasm_hash: &soroban_sdk::BytesN<32>,
+    ) -> Result<(), crate::AdminError> {
+        if !Self::is_wasm_approved(env, wasm_hash) {
+            return Err(crate::AdminError::WasmNotApproved);
+        }
+        Ok(())
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, StorageKey)]
+pub enum UpgradeAllowlistKey {
+    /// An approved wasm hash, in persistent storage.
+    ApprovedWasm(BytesN<32>),
+}
+
+pub struct ApprovedWasms<K = AdminSep>(PhantomData<K>);
+
+impl<K: Namespace> UpgradeAllowlist for ApprovedWasms<K> {
+    type Impl = Self;
+    const STORAGE_KEYS: StorageKeys = &[(K::NAMESPACE, UpgradeAllowlistKey::VARIANTS)];
+    fn is_wasm_approved(env: &Env, wasm_hash: &BytesN<32>) -> bool {
+        Persistent::get::<_, ()>(
+            env,
+            &storage_key::<K, _>(env, UpgradeAllowlistKey::ApprovedWasm(wasm_hash.clone())),
+        )
+        .is_some()
+    }
+
+    fn approve_wasm(
+        env: &Env,
+        approver: &Address,
+        wasm_hash: &BytesN<32>,
+    ) -> Result<(), AdminError> {
+        let key = storage_key::<K, _>(env, UpgradeAllowlistKey::ApprovedWasm(wasm_hash.clone()));
+        Persistent::set(env, &key, &());
+        events::wasm_approved(env, wasm_hash, approver);
+        Ok(())
+    }
+
+    fn revoke_wasm(
+        env: &Env,
+        approver: &Address,
+        wasm_hash: &BytesN<32>,
+    ) -> Result<(), AdminError> {
+        Self::require_approved(env, wasm_hash)?;
+        let key = storage_key::<K, _>(env, UpgradeAllowlistKey::ApprovedWasm(wasm_hash.clone()));
+        Persistent::remove(env, &key);
+        events::wasm_revoked(env, wasm_hash, approver);
+        Ok(())
+    }
+}
+
+impl<T: Administratable, N: UpgradeAllowlist> UpgradeAllowlist for AdministratableExt<T, N> {
+    type Impl = N;
+    fn approve_wasm(
+        env: &Env,
+        approver: &Address,
+        wasm_hash: &BytesN<32>,
+    ) -> Result<(), AdminError> {
+        require_admin_caller::<T>(env, approver, Self::approve_wasm_auth_args(env, wasm_hash))?;
+        N::approve_wasm(env, approver, wasm_hash)
+    }
+
+    fn revoke_wasm(
+        env: &Env,
+        approver: &Address,
+        wasm_hash: &BytesN<32>,
+    ) -> Result<(), AdminError> {
+        require_admin_caller::<T>(env, approver, Self::revoke_wasm_auth_args(env, wasm_hash))?;
+        N::revoke_wasm(env, approver, wasm_hash)
+    }
+}
+
+impl<T: AccessControl, N: UpgradeAllowlist> UpgradeAllowlist for AccessControlExt<T, N> {
+    type Impl = N;
+    fn approve_wasm(
+        env: &Env,
+        approver: &Address,
+        wasm_hash: &BytesN<32>,
+    ) -> Result<(), AdminError> {
+        T::require_role(env, &APPROVER_ROLE, approver)?;
+        N::approve_wasm(env, approver, wasm_hash)
+    }
+
+    fn revoke_wasm(
+        env: &Env,
+        approver: &Address,
+        wasm_hash: &BytesN<32>,
+    ) -> Result<(), AdminError> {
+        T::require_role(env, &APPROVER_ROLE, approver)?;
+        N::revoke_wasm(env, approver, wasm_hash)
+    }
+}
+
+/// Hashes can still be revoked while paused.
+impl<T: Pausable, N: UpgradeAllowlist> UpgradeAllowlist for PausableExt<T, N> {
+    type Impl = N;
+    fn approve_wasm(
+        env: &Env,
+        approver: &Address,
+        wasm_hash: &BytesN<32>,
+    ) -> Result<(), AdminError> {
+        T::require_not_paused(env)?;
+        N::approve_wasm(env, approver, wasm_hash)
+    }
+}
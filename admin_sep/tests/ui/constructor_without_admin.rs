@@ -0,0 +1,8 @@
+use admin_sep::{Admin, Administratable, Constructable, Constructor, derive_contract};
+use soroban_sdk::{Env, contract};
+
+#[contract]
+#[derive_contract(Administratable, Constructable)]
+pub struct Contract;
+
+fn main() {}
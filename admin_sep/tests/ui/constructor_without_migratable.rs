@@ -0,0 +1,22 @@
+use admin_sep::{
+    Admin, AdminError, Administratable, AdministratableExt, Constructable, Constructor, Migratable,
+    Migration, Migrator, derive_contract,
+};
+use soroban_sdk::{Env, contract};
+
+#[contract]
+#[derive_contract(
+    Administratable,
+    Migratable(ext = AdministratableExt, default = Migrator<Contract>),
+    Constructable(ext = AdministratableExt)
+)]
+pub struct Contract;
+
+impl Migration for Contract {
+    const VERSION: u32 = 1;
+    fn migrate(_env: &Env, _from_version: u32) -> Result<(), AdminError> {
+        Ok(())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use admin_sep::HasAdmin;
+use soroban_sdk::Address;
+
+#[derive(HasAdmin)]
+pub struct Args {
+    #[admin]
+    pub owner: Address,
+    #[admin]
+    pub other: Address,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use admin_sep::HasAdmin;
+use soroban_sdk::Address;
+
+#[derive(HasAdmin)]
+pub struct Args {
+    pub owner: Address,
+    pub count: u32,
+}
+
+fn main() {}
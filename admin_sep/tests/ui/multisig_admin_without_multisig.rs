@@ -0,0 +1,13 @@
+use admin_sep::{
+    Administratable, AdministratableExt, Constructable, Constructor, MultisigAdmin, derive_contract,
+};
+use soroban_sdk::{Env, contract};
+
+#[contract]
+#[derive_contract(
+    Administratable(default = MultisigAdmin<Contract>),
+    Constructable(ext = AdministratableExt)
+)]
+pub struct Contract;
+
+fn main() {}
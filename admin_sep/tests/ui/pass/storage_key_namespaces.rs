@@ -0,0 +1,27 @@
+use admin_sep::{
+    Admin, Administratable, AdministratableExt, Namespace, Pausable, Pauser, StorageKey,
+    derive_contract,
+};
+use soroban_sdk::{Env, contract, contracttype};
+
+pub struct MyNamespace;
+
+impl Namespace for MyNamespace {
+    const NAMESPACE: &'static str = "my_namespace";
+}
+
+#[contracttype]
+#[derive(StorageKey)]
+pub enum MyKey {
+    Paused,
+}
+
+#[contract]
+#[derive_contract(
+    storage_keys = [(MyNamespace, MyKey)],
+    Administratable,
+    Pausable(ext = AdministratableExt)
+)]
+pub struct Contract;
+
+fn main() {}
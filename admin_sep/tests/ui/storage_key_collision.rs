@@ -0,0 +1,21 @@
+use admin_sep::{
+    Admin, AdminSep, Administratable, AdministratableExt, Pausable, Pauser, StorageKey,
+    derive_contract,
+};
+use soroban_sdk::{Env, contract, contracttype};
+
+#[contracttype]
+#[derive(StorageKey)]
+pub enum MyKey {
+    Paused,
+}
+
+#[contract]
+#[derive_contract(
+    storage_keys = [(AdminSep, MyKey)],
+    Administratable,
+    Pausable(ext = AdministratableExt)
+)]
+pub struct Contract;
+
+fn main() {}
@@ -12,17 +12,23 @@ pub fn parse<T: deluxe::ParseMetaItem, I: syn::parse::Parse>(
 #[derive(deluxe::ParseMetaItem, Default)]
 pub struct MyTraitMacroArgs {
     #[deluxe(default)]
-    pub default: Option<syn::Ident>,
+    pub default: Option<syn::Type>,
     #[deluxe(default, rename = extension_required)]
     pub ext_required: bool,
     #[deluxe(default, rename = is_extension)]
     pub is_ext: bool,
+    /// The trait has an `init` run by wrapping `Constructable` in its extension.
+    #[deluxe(default)]
+    pub init_required: bool,
 }
 
 #[derive(deluxe::ParseMetaItem)]
 pub struct MyMacroArgs {
+    /// Storage keys used by the contract itself, as `(Namespace, KeyEnum)` pairs.
+    #[deluxe(default)]
+    pub storage_keys: Vec<syn::TypeTuple>,
     #[deluxe(rest)]
-    pub args: std::collections::HashMap<syn::Ident, InnerArgs>,
+    pub args: std::collections::BTreeMap<syn::Ident, InnerArgs>,
 }
 
 #[derive(ParseMetaItem)]
@@ -30,5 +36,8 @@ pub struct InnerArgs {
     #[deluxe(append, rename = ext)]
     pub exts: Vec<syn::Ident>,
     #[deluxe(default)]
-    pub default: Option<syn::Ident>,
+    pub default: Option<syn::Type>,
+    /// Type of the arguments of a generic contract trait, e.g. `Constructable(args = MyArgs)`.
+    #[deluxe(default)]
+    pub args: Option<syn::Type>,
 }
@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
+
 use deluxe::HasAttributes;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
 use quote::{format_ident, quote, ToTokens};
 use syn::{
-    punctuated::Punctuated, Attribute, FnArg, Item, ItemTrait, PatType, Signature, Token,
-    TraitItem, Type,
+    punctuated::Punctuated, Attribute, FnArg, GenericParam, Item, ItemTrait, PatType, Signature,
+    Token, TraitItem, Type, TypeParam,
 };
 
 use crate::{
@@ -21,7 +23,9 @@ pub fn derive_contract(args: &MyMacroArgs, trait_impls: &Item) -> TokenStream {
 }
 
 fn generate_method(
-    (trait_item, item_trait): (&syn::TraitItem, &syn::ItemTrait),
+    trait_item: &syn::TraitItem,
+    trait_path: &TokenStream,
+    args_param: Option<&Ident>,
 ) -> Option<(Option<TokenStream>, TokenStream)> {
     let syn::TraitItem::Fn(mut method) = trait_item.clone() else {
         return None;
@@ -47,7 +51,9 @@ fn generate_method(
         return Some((None, method_stream));
     }
     Some((
-        Some(generate_static_method(item_trait, sig, attrs, name, &args)),
+        Some(generate_static_method(
+            trait_path, args_param, sig, attrs, name, &args,
+        )),
         generate_trait_method(&method, name, &args),
     ))
 }
@@ -69,14 +75,17 @@ pub fn args_to_idents(inputs: &Punctuated<FnArg, Token!(,)>) -> Vec<&Ident> {
 }
 
 fn generate_static_method(
-    trait_name: &ItemTrait,
+    trait_path: &TokenStream,
+    args_param: Option<&Ident>,
     sig: &Signature,
     attrs: &[Attribute],
     name: &Ident,
     args: &[&Ident],
 ) -> TokenStream {
-    let trait_name = &trait_name.ident;
-    let output = &sig.output;
+    let output = args_to_dollar_args(
+        crate_to_dollar_crate(sig.output.to_token_stream()),
+        args_param,
+    );
 
     // Transform inputs and generate call arguments
     let (transformed_inputs, call_args): (Vec<_>, Vec<_>) = sig
@@ -86,6 +95,7 @@ fn generate_static_method(
         .filter_map(|(input, arg_name)| {
             if let FnArg::Typed(PatType { pat, ty, .. }) = input {
                 let (new_ty, call_expr) = transform_type_and_call(ty, arg_name);
+                let new_ty = args_to_dollar_args(crate_to_dollar_crate(new_ty), args_param);
                 Some((quote! { #pat: #new_ty }, call_expr))
             } else {
                 // Skip 'self' parameters
@@ -97,7 +107,7 @@ fn generate_static_method(
     quote! {
         #(#attrs)*
         pub fn #name(#(#transformed_inputs),*) #output {
-            <$contract_name as #trait_name>::#name(#(#call_args),*)
+            <$contract_name as #trait_path>::#name(#(#call_args),*)
         }
     }
 }
@@ -119,6 +129,65 @@ fn transform_type_and_call(ty: &Type, arg_name: &Ident) -> (TokenStream, TokenSt
     }
 }
 
+/// Paths starting with `crate` refer to the crate defining the contract trait, e.g.
+/// `Result<(), crate::MyError>`. The generated wrappers are expanded in the contract's crate,
+/// so they must use `$crate` instead.
+fn crate_to_dollar_crate(tokens: TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Ident(ident) if ident == "crate" => quote! { $crate },
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), crate_to_dollar_crate(group.stream()));
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group).into_token_stream()
+            }
+            tt => tt.into_token_stream(),
+        })
+        .collect()
+}
+
+/// The type parameter of a generic contract trait, if it has one.
+fn args_param(input_trait: &ItemTrait) -> Result<Option<TypeParam>, Error> {
+    let params = &input_trait.generics.params;
+    match params.first() {
+        None => Ok(None),
+        Some(GenericParam::Type(param)) if params.len() == 1 => Ok(Some(param.clone())),
+        Some(_) => {
+            let message = format!(
+                "The contract trait `{}` can only have a single type parameter, its arguments",
+                input_trait.ident
+            );
+            Err(Error::Stream(quote! { compile_error!(#message); }))
+        }
+    }
+}
+
+/// The type parameter of a generic contract trait, e.g. `T` of `Constructable<T>`, is the
+/// `args` type passed to `derive_contract`, which the generated wrappers receive as the raw
+/// tokens `$($args)+`, since `#[contractimpl]` can't see through a `$args:ty` fragment.
+fn args_to_dollar_args(tokens: TokenStream, args_param: Option<&Ident>) -> TokenStream {
+    let Some(args_param) = args_param else {
+        return tokens;
+    };
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Ident(ident) if ident == *args_param => quote! { $($args)+ },
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    args_to_dollar_args(group.stream(), Some(args_param)),
+                );
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group).into_token_stream()
+            }
+            tt => tt.into_token_stream(),
+        })
+        .collect()
+}
+
 fn generate_trait_method(method: &syn::TraitItemFn, name: &Ident, args: &[&Ident]) -> TokenStream {
     let mut method = method.clone();
     method.default = Some(syn::parse_quote! {
@@ -129,11 +198,76 @@ fn generate_trait_method(method: &syn::TraitItemFn, name: &Ident, args: &[&Ident
     method.to_token_stream()
 }
 
+/// Parameters naming the address whose auth a method requires, which isn't bound to its own
+/// auth.
+const AUTHORIZING_ARGS: &[&str] = &["caller", "operator", "approver"];
+
+/// A provided `<method>_auth_args` for each exported method returning a `Result`, binding the
+/// method's arguments other than `env` and the caller, for `require_auth_for_args`.
+fn generate_auth_args_method(trait_item: &TraitItem) -> Option<TraitItem> {
+    let TraitItem::Fn(method) = trait_item else {
+        return None;
+    };
+    let sig = &method.sig;
+    let returns_result = matches!(
+        &sig.output,
+        syn::ReturnType::Type(_, ty) if matches!(
+            &**ty,
+            Type::Path(path) if path.path.segments.last().is_some_and(|seg| seg.ident == "Result")
+        )
+    );
+    if has_attr(&method.attrs, "internal")
+        || sig.ident.to_string().starts_with("__")
+        || !returns_result
+    {
+        return None;
+    }
+    let mut inputs = sig.inputs.iter().filter_map(|input| match input {
+        FnArg::Typed(pat_type) => Some(pat_type),
+        FnArg::Receiver(_) => None,
+    });
+    let env = inputs.next()?;
+    let env_pat = &env.pat;
+    let env_ref = if matches!(&*env.ty, Type::Reference(_)) {
+        quote! { #env_pat }
+    } else {
+        quote! { &#env_pat }
+    };
+    let (params, args): (Vec<_>, Vec<_>) = inputs
+        .filter(|PatType { pat, .. }| {
+            !matches!(&**pat, syn::Pat::Ident(ident)
+                if AUTHORIZING_ARGS.iter().any(|name| ident.ident == name))
+        })
+        .map(|PatType { pat, ty, .. }| {
+            let ty = match &**ty {
+                Type::Reference(_) => quote! { #ty },
+                _ => quote! { &#ty },
+            };
+            (
+                quote! { #pat: #ty },
+                quote! {
+                    soroban_sdk::IntoVal::<soroban_sdk::Env, soroban_sdk::Val>::into_val(
+                        #pat, #env_ref,
+                    )
+                },
+            )
+        })
+        .unzip();
+    let name = format_ident!("{}_auth_args", sig.ident);
+    Some(syn::parse_quote! {
+        #[doc(hidden)]
+        fn #name(#env, #(#params),*) -> soroban_sdk::Vec<soroban_sdk::Val> {
+            soroban_sdk::vec![#env_ref, #(#args),*]
+        }
+    })
+}
+
 fn inner_generate(
     MyTraitMacroArgs {
         default,
         ext_required,
         is_ext,
+        init_required,
     }: &MyTraitMacroArgs,
     item: &Item,
 ) -> Result<TokenStream, Error> {
@@ -142,26 +276,50 @@ fn inner_generate(
             quote! { compile_error!("Input must be a trait"); },
         ));
     };
+    let trait_ident = &input_trait.ident;
+    let args_param = args_param(input_trait)?;
+    let trait_path = if args_param.is_some() {
+        quote! { #trait_ident<$($args)+> }
+    } else {
+        quote! { #trait_ident }
+    };
     let (generated_methods, trait_methods): (Vec<_>, Vec<_>) = input_trait
         .items
         .iter()
-        .zip(std::iter::repeat(input_trait))
-        .filter_map(generate_method)
+        .filter_map(|item| {
+            generate_method(
+                item,
+                &trait_path,
+                args_param.as_ref().map(|param| &param.ident),
+            )
+        })
         .unzip();
 
-    let trait_ident = &input_trait.ident;
     let macro_rules_name = trait_ident;
-    let attrs = input_trait.attrs.as_slice();
 
     let mut trait_ = input_trait.clone();
     let mut items = trait_methods
         .into_iter()
         .map(syn::parse2)
         .collect::<Result<Vec<TraitItem>, _>>()?;
+    items.extend(
+        input_trait
+            .items
+            .iter()
+            .filter_map(generate_auth_args_method),
+    );
+    let (_, ty_generics, _) = input_trait.generics.split_for_impl();
     items.insert(
         0,
         syn::parse_quote! {
-            type Impl: #trait_ident;
+            type Impl: #trait_ident #ty_generics;
+        },
+    );
+    items.insert(
+        1,
+        syn::parse_quote! {
+            const STORAGE_KEYS: &'static [(&'static str, &'static [&'static str])] =
+                <Self::Impl as #trait_ident #ty_generics>::STORAGE_KEYS;
         },
     );
     trait_.items = items;
@@ -203,15 +361,100 @@ One should be passed, e.g. default = MyDefaultImpl"
     } else {
         quote! {}
     };
+    // `derive_contract` passes the extensions of `Constructable` to check it's wrapped in
+    // the trait's extension.
+    let init_check = if *init_required {
+        if !*is_ext {
+            return Err(syn::Error::new_spanned(
+                trait_ident,
+                "init_required needs is_extension = true",
+            )
+            .into());
+        }
+        let extension_strukt = format_ident!("{}Ext", trait_ident);
+        let message = format!(
+            "The contract trait `{trait_ident}` needs its `init` run by the constructor. \
+Wrap `Constructable` in its extension, e.g. Constructable(ext = {extension_strukt})"
+        );
+        quote! {
+            (@init []) => {
+                compile_error!(#message);
+            };
+            (@init [#extension_strukt $(, $ext:ident)*]) => {};
+            (@init [$first:ident $(, $ext:ident)*]) => {
+                #macro_rules_name!(@init [$($ext),*]);
+            };
+        }
+    } else {
+        quote! {
+            (@init [$($ext:ident),*]) => {};
+        }
+    };
     let docs = input_trait
         .attrs()
         .iter()
         .filter(|attr| attr.path().is_ident("doc"))
         .collect::<Vec<_>>();
 
+    if let Some(args_param) = args_param {
+        let default_args = args_param.default.map_or_else(
+            || {
+                let message = format!(
+                    "The contract trait `{trait_ident}` needs the type of its arguments, \
+e.g. args = MyArgs"
+                );
+                quote! { compile_error!(#message) }
+            },
+            |default| quote! { #default },
+        );
+        return Ok(quote! {
+        #trait_
+        #extension_type
+        #(#docs)*
+        #[macro_export]
+        macro_rules! #macro_rules_name {
+            ($contract_name:ident) => {
+                #macro_rules_name!($contract_name, args = [#default_args]);
+            };
+            ($contract_name:ident, args = [$($args:tt)+]) => {
+                #ensure_default
+                #macro_rules_name!($contract_name, args = [$($args)+], #default_impl);
+            };
+            ($contract_name:ident, args = [$($args:tt)+], $($impl_type:tt)+) => {
+                #macro_rules_name!(@dispatch $contract_name, [$($args)+], $($impl_type)+);
+            };
+            ($contract_name:ident, $($impl_type:tt)+) => {
+                #macro_rules_name!($contract_name, args = [#default_args], $($impl_type)+);
+            };
+            (@dispatch $contract_name:ident, [$($args:tt)+], $impl_name:ident) => {
+                #first_case
+                impl #trait_ident<$($args)+> for $contract_name {
+                    type Impl = $impl_name;
+                }
+                #[soroban_sdk::contractimpl]
+                impl $contract_name {
+                    #(#generated_methods)*
+                }
+            };
+            (@dispatch $contract_name:ident, [$($args:tt)+], $($impl_type:tt)+) => {
+                impl #trait_ident<$($args)+> for $contract_name {
+                    type Impl = $($impl_type)+;
+                }
+                #[soroban_sdk::contractimpl]
+                impl $contract_name {
+                    #(#generated_methods)*
+                }
+            };
+            #init_check
+            () => {
+                #default_impl
+            };
+        }
+        });
+    }
+
     let output = quote! {
 
-    #(#attrs)*
     #trait_
     #extension_type
     #(#docs)*
@@ -246,6 +489,7 @@ One should be passed, e.g. default = MyDefaultImpl"
                 #(#generated_methods)*
             }
         };
+        #init_check
         () => {
             #default_impl
         };
@@ -262,10 +506,17 @@ pub fn derive_contract_inner(args: &MyMacroArgs, trait_impls: &Item) -> Result<T
         ));
     };
     let strukt_name = &strukt.ident;
-    let macro_calls = args
-        .args
-        .iter()
-        .map(|(trait_ident, InnerArgs { exts, default })| {
+    let storage_key_checks = storage_key_checks(strukt_name, &args.storage_keys, &args.args)?;
+    let init_checks = init_checks(&args.args);
+    let macro_calls = args.args.iter().map(
+        |(
+            trait_ident,
+            InnerArgs {
+                exts,
+                default,
+                args,
+            },
+        )| {
             let init = default.as_ref().map_or_else(
                 || quote! {#trait_ident!()},
                 |default| {
@@ -276,13 +527,157 @@ pub fn derive_contract_inner(args: &MyMacroArgs, trait_impls: &Item) -> Result<T
                 init,
                 |acc, extension| quote! { #extension<#strukt_name, #acc> },
             );
+            let args = args.as_ref().map(|args| quote! { args = [#args], });
             quote! {
-                #trait_ident!(#strukt_name, #default_impl);
+                #trait_ident!(#strukt_name, #args #default_impl);
             }
-        });
+        },
+    );
     Ok(quote! {
         #strukt
         #(#macro_calls)*
+        #init_checks
+        #storage_key_checks
+    })
+}
+
+/// Fail to compile if a composed trait's `init` isn't run by `Constructable`, since
+/// nothing else would initialize it.
+fn init_checks(traits: &BTreeMap<Ident, InnerArgs>) -> TokenStream {
+    let Some((constructable, InnerArgs { exts, .. })) = traits
+        .iter()
+        .find(|(trait_ident, _)| *trait_ident == "Constructable")
+    else {
+        return quote! {};
+    };
+    let checks = traits
+        .keys()
+        .filter(|trait_ident| *trait_ident != constructable)
+        .map(|trait_ident| quote! { #trait_ident!(@init [#(#exts),*]); });
+    quote! { #(#checks)* }
+}
+
+/// Fail to compile if two of the composed traits, or a trait and the contract's own
+/// `storage_keys`, register a variant of a storage key enum in the same namespace.
+fn storage_key_checks(
+    strukt_name: &Ident,
+    contract_keys: &[syn::TypeTuple],
+    traits: &BTreeMap<Ident, InnerArgs>,
+) -> Result<TokenStream, Error> {
+    let mut owners = traits
+        .iter()
+        .map(|(trait_ident, InnerArgs { args, .. })| {
+            let args = args.as_ref().map(|args| quote! { <#args> });
+            (
+                trait_ident.to_string(),
+                quote! { <#strukt_name as #trait_ident #args>::STORAGE_KEYS },
+            )
+        })
+        .collect::<Vec<_>>();
+    if !contract_keys.is_empty() {
+        let keys = contract_keys
+            .iter()
+            .map(|pair| {
+                let [namespace, key] = pair.elems.iter().collect::<Vec<_>>()[..] else {
+                    return Err(syn::Error::new_spanned(
+                        pair,
+                        "Storage keys are passed as (Namespace, KeyEnum)",
+                    ));
+                };
+                Ok(quote! {
+                    (
+                        <#namespace as ::admin_sep::Namespace>::NAMESPACE,
+                        <#key as ::admin_sep::StorageKey>::VARIANTS,
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        owners.push((strukt_name.to_string(), quote! { &[#(#keys),*] }));
+    }
+    if owners.len() < 2 {
+        return Ok(quote! {});
+    }
+    let checks = owners.iter().enumerate().flat_map(|(i, (a, a_keys))| {
+        owners[i + 1..].iter().map(move |(b, b_keys)| {
+            quote! {
+                check(#a, #b, collision(#a_keys, #b_keys));
+            }
+        })
+    });
+    Ok(quote! {
+        const _: () = {
+            const fn eq(a: &str, b: &str) -> bool {
+                let (a, b) = (a.as_bytes(), b.as_bytes());
+                if a.len() != b.len() {
+                    return false;
+                }
+                let mut i = 0;
+                while i < a.len() {
+                    if a[i] != b[i] {
+                        return false;
+                    }
+                    i += 1;
+                }
+                true
+            }
+            /// The first namespace and variant registered by both `a` and `b`.
+            const fn collision<'a>(
+                a: &[(&'a str, &'a [&'a str])],
+                b: &[(&str, &[&str])],
+            ) -> Option<(&'a str, &'a str)> {
+                let mut i = 0;
+                while i < a.len() {
+                    let mut j = 0;
+                    while j < b.len() {
+                        if eq(a[i].0, b[j].0) {
+                            let mut k = 0;
+                            while k < a[i].1.len() {
+                                let mut l = 0;
+                                while l < b[j].1.len() {
+                                    if eq(a[i].1[k], b[j].1[l]) {
+                                        return Some((a[i].0, a[i].1[k]));
+                                    }
+                                    l += 1;
+                                }
+                                k += 1;
+                            }
+                        }
+                        j += 1;
+                    }
+                    i += 1;
+                }
+                None
+            }
+            /// Panic naming the owners `a` and `b` of the colliding key, since a constant's
+            /// panic message can't be formatted.
+            const fn check(a: &str, b: &str, collision: Option<(&str, &str)>) {
+                let Some((namespace, variant)) = collision else {
+                    return;
+                };
+                let parts = [
+                    "`", a, "` and `", b, "` register the same storage key `", variant,
+                    "` in the namespace `", namespace, "`",
+                ];
+                let mut buf = [0u8; 512];
+                let mut len = 0;
+                let mut i = 0;
+                while i < parts.len() {
+                    let bytes = parts[i].as_bytes();
+                    let mut j = 0;
+                    while j < bytes.len() && len < buf.len() {
+                        buf[len] = bytes[j];
+                        len += 1;
+                        j += 1;
+                    }
+                    i += 1;
+                }
+                match core::str::from_utf8(buf.split_at(len).0) {
+                    Ok(message) => panic!("{}", message),
+                    Err(_) => panic!("Two contract traits register the same storage key"),
+                }
+            }
+            #(#checks)*
+        };
     })
 }
 
@@ -305,7 +700,7 @@ mod tests {
                 }
             }
         };
-        let default = Some(format_ident!("Admin"));
+        let default = Some(syn::parse_quote!(Admin));
         let result: TokenStream = generate(
             &MyTraitMacroArgs {
                 default,
@@ -318,6 +713,8 @@ mod tests {
         let output = quote! {
         pub trait Administratable {
             type Impl: Administratable;
+            const STORAGE_KEYS: &'static [(&'static str, &'static [&'static str])] =
+                <Self::Impl as Administratable>::STORAGE_KEYS;
             #[doc = r" Get current admin"]
             fn admin_get(env: Env) -> soroban_sdk::Address {
                 Self::Impl::admin_get(env)
@@ -375,6 +772,8 @@ mod tests {
                 }
             };
 
+            (@init [$($ext: ident),*]) => {};
+
             () => {
                 Admin
             };
@@ -385,6 +784,300 @@ mod tests {
         equal_tokens(&output, &result);
     }
 
+    #[test]
+    fn fallible() {
+        let input: Item = syn::parse_quote! {
+            pub trait Upgradable {
+                fn upgrade(env: &Env, wasm_hash: BytesN<32>) -> Result<(), crate::AdminError>;
+                #[internal]
+                fn require_upgrader(env: &Env) -> Result<(), crate::AdminError>;
+            }
+        };
+        let default = Some(syn::parse_quote!(Upgrader));
+        let result: TokenStream = generate(
+            &MyTraitMacroArgs {
+                default,
+                ..Default::default()
+            },
+            &input,
+        );
+        println!("{}", format_snippet(&result.to_string()));
+
+        let output = quote! {
+        pub trait Upgradable {
+            type Impl: Upgradable;
+            const STORAGE_KEYS: &'static [(&'static str, &'static [&'static str])] =
+                <Self::Impl as Upgradable>::STORAGE_KEYS;
+            fn upgrade(env: &Env, wasm_hash: BytesN<32>) -> Result<(), crate::AdminError> {
+                Self::Impl::upgrade(env, wasm_hash)
+            }
+            fn require_upgrader(env: &Env) -> Result<(), crate::AdminError> {
+                Self::Impl::require_upgrader(env)
+            }
+            #[doc(hidden)]
+            fn upgrade_auth_args(
+                env: &Env,
+                wasm_hash: &BytesN<32>
+            ) -> soroban_sdk::Vec<soroban_sdk::Val> {
+                soroban_sdk::vec![
+                    env,
+                    soroban_sdk::IntoVal::<soroban_sdk::Env, soroban_sdk::Val>::into_val(
+                        wasm_hash, env,
+                    )
+                ]
+            }
+        }
+        #[macro_export]
+        macro_rules! Upgradable {
+            ($contract_name: ident) => {
+                Upgradable!($contract_name, Upgrader);
+            };
+
+            ($contract_name: ident, $($impl_type: tt)+) => {
+                Upgradable!(@dispatch $contract_name, $($impl_type)+);
+            };
+
+            (@dispatch $contract_name: ident, $impl_name: ident) => {
+                impl Upgradable for $contract_name {
+                    type Impl = $impl_name;
+                }
+
+                #[soroban_sdk::contractimpl]
+                impl $contract_name {
+                    pub fn upgrade(env: Env, wasm_hash: BytesN<32>) -> Result<(), $crate::AdminError> {
+                        < $contract_name as Upgradable >::upgrade(&env, wasm_hash)
+                    }
+                }
+            };
+
+            (@dispatch $contract_name: ident, $($impl_type: tt)+) => {
+                impl Upgradable for $contract_name {
+                    type Impl = $($impl_type)+;
+                }
+
+                #[soroban_sdk::contractimpl]
+                impl $contract_name {
+                    pub fn upgrade(env: Env, wasm_hash: BytesN<32>) -> Result<(), $crate::AdminError> {
+                        < $contract_name as Upgradable >::upgrade(&env, wasm_hash)
+                    }
+                }
+            };
+
+            (@init [$($ext: ident),*]) => {};
+
+            () => {
+                Upgrader
+            };
+        }
+                };
+        equal_tokens(&output, &result);
+    }
+
+    #[test]
+    fn generic() {
+        let input: Item = syn::parse_quote! {
+            pub trait Constructable<T = soroban_sdk::Address> {
+                fn __constructor(env: &Env, args: T);
+            }
+        };
+        let default = Some(syn::parse_quote!(Constructor));
+        let result: TokenStream = generate(
+            &MyTraitMacroArgs {
+                default,
+                ..Default::default()
+            },
+            &input,
+        );
+        println!("{}", format_snippet(&result.to_string()));
+
+        let output = quote! {
+        pub trait Constructable<T = soroban_sdk::Address> {
+            type Impl: Constructable<T>;
+            const STORAGE_KEYS: &'static [(&'static str, &'static [&'static str])] =
+                <Self::Impl as Constructable<T> >::STORAGE_KEYS;
+            fn __constructor(env: &Env, args: T) {
+                Self::Impl::__constructor(env, args)
+            }
+        }
+        #[macro_export]
+        macro_rules! Constructable {
+            ($contract_name: ident) => {
+                Constructable!($contract_name, args = [soroban_sdk::Address]);
+            };
+            ($contract_name: ident, args = [$($args: tt)+]) => {
+                Constructable!($contract_name, args = [$($args)+], Constructor);
+            };
+            ($contract_name: ident, args = [$($args: tt)+], $($impl_type: tt)+) => {
+                Constructable!(@dispatch $contract_name, [$($args)+], $($impl_type)+);
+            };
+            ($contract_name: ident, $($impl_type: tt)+) => {
+                Constructable!($contract_name, args = [soroban_sdk::Address], $($impl_type)+);
+            };
+            (@dispatch $contract_name: ident, [$($args: tt)+], $impl_name: ident) => {
+                impl Constructable<$($args)+> for $contract_name {
+                    type Impl = $impl_name;
+                }
+                #[soroban_sdk::contractimpl]
+                impl $contract_name {
+                    pub fn __constructor(env: Env, args: $($args)+) {
+                        < $contract_name as Constructable<$($args)+> >::__constructor(&env, args)
+                    }
+                }
+            };
+            (@dispatch $contract_name: ident, [$($args: tt)+], $($impl_type: tt)+) => {
+                impl Constructable<$($args)+> for $contract_name {
+                    type Impl = $($impl_type)+;
+                }
+                #[soroban_sdk::contractimpl]
+                impl $contract_name {
+                    pub fn __constructor(env: Env, args: $($args)+) {
+                        < $contract_name as Constructable<$($args)+> >::__constructor(&env, args)
+                    }
+                }
+            };
+            (@init [$($ext: ident),*]) => {};
+
+            () => {
+                Constructor
+            };
+        }
+                };
+        equal_tokens(&output, &result);
+    }
+
+    #[test]
+    fn init_required() {
+        let input: Item = syn::parse_quote! {
+            pub trait Guardian {
+                #[internal]
+                fn init(env: &Env);
+            }
+        };
+        let default = Some(syn::parse_quote!(GuardianRole));
+        let result: TokenStream = generate(
+            &MyTraitMacroArgs {
+                default,
+                is_ext: true,
+                init_required: true,
+                ..Default::default()
+            },
+            &input,
+        );
+        println!("{}", format_snippet(&result.to_string()));
+
+        let output = quote! {
+        pub trait Guardian {
+            type Impl: Guardian;
+            const STORAGE_KEYS: &'static [(&'static str, &'static [&'static str])] =
+                <Self::Impl as Guardian>::STORAGE_KEYS;
+            fn init(env: &Env) {
+                Self::Impl::init(env)
+            }
+        }
+        pub struct GuardianExt<T: Guardian, N>(
+            core::marker::PhantomData<T>,
+            core::marker::PhantomData<N>,
+        );
+        #[macro_export]
+        macro_rules! Guardian {
+            ($contract_name: ident) => {
+                Guardian!($contract_name, GuardianRole);
+            };
+
+            ($contract_name: ident, $($impl_type: tt)+) => {
+                Guardian!(@dispatch $contract_name, $($impl_type)+);
+            };
+
+            (@dispatch $contract_name: ident, $impl_name: ident) => {
+                impl Guardian for $contract_name {
+                    type Impl = $impl_name;
+                }
+
+                #[soroban_sdk::contractimpl]
+                impl $contract_name {}
+            };
+
+            (@dispatch $contract_name: ident, $($impl_type: tt)+) => {
+                impl Guardian for $contract_name {
+                    type Impl = $($impl_type)+;
+                }
+
+                #[soroban_sdk::contractimpl]
+                impl $contract_name {}
+            };
+
+            (@init []) => {
+                compile_error!("The contract trait `Guardian` needs its `init` run by the constructor. Wrap `Constructable` in its extension, e.g. Constructable(ext = GuardianExt)");
+            };
+
+            (@init [GuardianExt $(, $ext: ident)*]) => {};
+
+            (@init [$first: ident $(, $ext: ident)*]) => {
+                Guardian!(@init [$($ext),*]);
+            };
+
+            () => {
+                GuardianRole
+            };
+        }
+                };
+        equal_tokens(&output, &result);
+    }
+
+    #[test]
+    fn init_checks_constructable() {
+        let args = [
+            (
+                format_ident!("Constructable"),
+                InnerArgs {
+                    exts: vec![format_ident!("AdministratableExt")],
+                    default: None,
+                    args: None,
+                },
+            ),
+            (
+                format_ident!("Administratable"),
+                InnerArgs {
+                    exts: vec![],
+                    default: None,
+                    args: None,
+                },
+            ),
+        ];
+        let output = quote! {
+            Administratable!(@init [AdministratableExt]);
+        };
+        equal_tokens(&output, &init_checks(&args.into_iter().collect()));
+    }
+
+    #[test]
+    fn derive_args() {
+        let input: Item = syn::parse_quote! {
+            pub struct Contract;
+        };
+        let args = vec![(
+            format_ident!("Constructable"),
+            InnerArgs {
+                exts: vec![format_ident!("AdministratableExt")],
+                default: None,
+                args: Some(syn::parse_quote!(CustomArgs)),
+            },
+        )];
+
+        let result = derive_contract(
+            &MyMacroArgs {
+                storage_keys: vec![],
+                args: args.into_iter().collect(),
+            },
+            &input,
+        );
+        let output = quote! {
+        pub struct Contract;
+        Constructable!(Contract, args = [CustomArgs], AdministratableExt<Contract, Constructable!()>);
+        };
+        equal_tokens(&output, &result);
+    }
+
     #[test]
     fn derive() {
         let input: Item = syn::parse_quote! {
@@ -396,6 +1089,7 @@ mod tests {
                 InnerArgs {
                     exts: vec![],
                     default: None,
+                    args: None,
                 },
             ),
             (
@@ -403,12 +1097,14 @@ mod tests {
                 InnerArgs {
                     exts: vec![format_ident!("AdministratableExt")],
                     default: None,
+                    args: None,
                 },
             ),
         ];
 
         let result = derive_contract(
             &MyMacroArgs {
+                storage_keys: vec![],
                 args: args.into_iter().collect(),
             },
             &input,
@@ -416,8 +1112,227 @@ mod tests {
         println!("{}", format_snippet(&result.to_string()));
         let output = quote! {
         pub struct Contract;
-        Upgradable ! (Contract , AdministratableExt < Contract , Upgradable ! () >);
         Administratable!(Contract, Administratable!());
+        Upgradable!(Contract, AdministratableExt<Contract, Upgradable!()>);
+        const _: () = {
+            const fn eq(a: &str, b: &str) -> bool {
+                let (a, b) = (a.as_bytes(), b.as_bytes());
+                if a.len() != b.len() {
+                    return false;
+                }
+                let mut i = 0;
+                while i < a.len() {
+                    if a[i] != b[i] {
+                        return false;
+                    }
+                    i += 1;
+                }
+                true
+            }
+            /// The first namespace and variant registered by both `a` and `b`.
+            const fn collision<'a>(
+                a: &[(&'a str, &'a [&'a str])],
+                b: &[(&str, &[&str])],
+            ) -> Option<(&'a str, &'a str)> {
+                let mut i = 0;
+                while i < a.len() {
+                    let mut j = 0;
+                    while j < b.len() {
+                        if eq(a[i].0, b[j].0) {
+                            let mut k = 0;
+                            while k < a[i].1.len() {
+                                let mut l = 0;
+                                while l < b[j].1.len() {
+                                    if eq(a[i].1[k], b[j].1[l]) {
+                                        return Some((a[i].0, a[i].1[k]));
+                                    }
+                                    l += 1;
+                                }
+                                k += 1;
+                            }
+                        }
+                        j += 1;
+                    }
+                    i += 1;
+                }
+                None
+            }
+            /// Panic naming the owners `a` and `b` of the colliding key, since a constant's
+            /// panic message can't be formatted.
+            const fn check(a: &str, b: &str, collision: Option<(&str, &str)>) {
+                let Some((namespace, variant)) = collision else {
+                    return;
+                };
+                let parts = [
+                    "`", a, "` and `", b, "` register the same storage key `", variant,
+                    "` in the namespace `", namespace, "`",
+                ];
+                let mut buf = [0u8; 512];
+                let mut len = 0;
+                let mut i = 0;
+                while i < parts.len() {
+                    let bytes = parts[i].as_bytes();
+                    let mut j = 0;
+                    while j < bytes.len() && len < buf.len() {
+                        buf[len] = bytes[j];
+                        len += 1;
+                        j += 1;
+                    }
+                    i += 1;
+                }
+                match core::str::from_utf8(buf.split_at(len).0) {
+                    Ok(message) => panic!("{}", message),
+                    Err(_) => panic!("Two contract traits register the same storage key"),
+                }
+            }
+            check(
+                "Administratable",
+                "Upgradable",
+                collision(
+                    <Contract as Administratable>::STORAGE_KEYS,
+                    <Contract as Upgradable>::STORAGE_KEYS
+                )
+            );
+        };
+        };
+        equal_tokens(&output, &result);
+    }
+
+    #[test]
+    fn derive_generic_default() {
+        let input: Item = syn::parse_quote! {
+            pub struct Contract;
+        };
+        let args = vec![(
+            format_ident!("Upgradable"),
+            InnerArgs {
+                exts: vec![format_ident!("AdministratableExt")],
+                default: Some(syn::parse_quote!(Upgrader<MyNamespace>)),
+                args: None,
+            },
+        )];
+
+        let result = derive_contract(
+            &MyMacroArgs {
+                storage_keys: vec![],
+                args: args.into_iter().collect(),
+            },
+            &input,
+        );
+        let output = quote! {
+        pub struct Contract;
+        Upgradable!(Contract, AdministratableExt<Contract, Upgrader<MyNamespace> >);
+        };
+        equal_tokens(&output, &result);
+    }
+
+    #[test]
+    fn derive_contract_storage_keys() {
+        let input: Item = syn::parse_quote! {
+            pub struct Contract;
+        };
+        let args = vec![(
+            format_ident!("Administratable"),
+            InnerArgs {
+                exts: vec![],
+                default: None,
+                args: None,
+            },
+        )];
+
+        let result = derive_contract(
+            &MyMacroArgs {
+                storage_keys: vec![syn::parse_quote!((MyNamespace, MyKey))],
+                args: args.into_iter().collect(),
+            },
+            &input,
+        );
+        let output = quote! {
+        pub struct Contract;
+        Administratable!(Contract, Administratable!());
+        const _: () = {
+            const fn eq(a: &str, b: &str) -> bool {
+                let (a, b) = (a.as_bytes(), b.as_bytes());
+                if a.len() != b.len() {
+                    return false;
+                }
+                let mut i = 0;
+                while i < a.len() {
+                    if a[i] != b[i] {
+                        return false;
+                    }
+                    i += 1;
+                }
+                true
+            }
+            /// The first namespace and variant registered by both `a` and `b`.
+            const fn collision<'a>(
+                a: &[(&'a str, &'a [&'a str])],
+                b: &[(&str, &[&str])],
+            ) -> Option<(&'a str, &'a str)> {
+                let mut i = 0;
+                while i < a.len() {
+                    let mut j = 0;
+                    while j < b.len() {
+                        if eq(a[i].0, b[j].0) {
+                            let mut k = 0;
+                            while k < a[i].1.len() {
+                                let mut l = 0;
+                                while l < b[j].1.len() {
+                                    if eq(a[i].1[k], b[j].1[l]) {
+                                        return Some((a[i].0, a[i].1[k]));
+                                    }
+                                    l += 1;
+                                }
+                                k += 1;
+                            }
+                        }
+                        j += 1;
+                    }
+                    i += 1;
+                }
+                None
+            }
+            /// Panic naming the owners `a` and `b` of the colliding key, since a constant's
+            /// panic message can't be formatted.
+            const fn check(a: &str, b: &str, collision: Option<(&str, &str)>) {
+                let Some((namespace, variant)) = collision else {
+                    return;
+                };
+                let parts = [
+                    "`", a, "` and `", b, "` register the same storage key `", variant,
+                    "` in the namespace `", namespace, "`",
+                ];
+                let mut buf = [0u8; 512];
+                let mut len = 0;
+                let mut i = 0;
+                while i < parts.len() {
+                    let bytes = parts[i].as_bytes();
+                    let mut j = 0;
+                    while j < bytes.len() && len < buf.len() {
+                        buf[len] = bytes[j];
+                        len += 1;
+                        j += 1;
+                    }
+                    i += 1;
+                }
+                match core::str::from_utf8(buf.split_at(len).0) {
+                    Ok(message) => panic!("{}", message),
+                    Err(_) => panic!("Two contract traits register the same storage key"),
+                }
+            }
+            check(
+                "Administratable",
+                "Contract",
+                collision(
+                    <Contract as Administratable>::STORAGE_KEYS,
+                    &[(
+                        <MyNamespace as ::admin_sep::Namespace>::NAMESPACE,
+                        <MyKey as ::admin_sep::StorageKey>::VARIANTS,
+                    )]
+                )
+            );
+        };
         };
         equal_tokens(&output, &result);
     }
@@ -0,0 +1,110 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Data, DeriveInput, Fields, Index};
+
+use crate::{error::Error, util::has_attr};
+
+pub fn derive(input: &DeriveInput) -> TokenStream {
+    derive_inner(input).unwrap_or_else(Into::into)
+}
+
+fn derive_inner(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(
+            syn::Error::new_spanned(input, "HasAdmin can only be derived for structs").into(),
+        );
+    };
+    let mut admin_fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| has_attr(&field.attrs, "admin"));
+    let Some((i, field)) = admin_fields.next() else {
+        return Err(match data.fields {
+            Fields::Unit => {
+                syn::Error::new_spanned(input, "HasAdmin can't be derived for a unit struct")
+            }
+            _ => syn::Error::new_spanned(
+                &data.fields,
+                "HasAdmin needs the admin's field to be marked with #[admin]",
+            ),
+        }
+        .into());
+    };
+    if let Some((_, second)) = admin_fields.next() {
+        return Err(
+            syn::Error::new_spanned(second, "Only one field can be marked with #[admin]").into(),
+        );
+    }
+    let admin_field = field.ident.as_ref().map_or_else(
+        || Index::from(i).into_token_stream(),
+        ToTokens::to_token_stream,
+    );
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::admin_sep::HasAdmin for #name #ty_generics #where_clause {
+            fn admin(&self) -> &::soroban_sdk::Address {
+                &self.#admin_field
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::*;
+
+    #[test]
+    fn named() {
+        let input: DeriveInput = syn::parse_quote! {
+            pub struct Args {
+                pub count: u32,
+                #[admin]
+                pub owner: Address,
+            }
+        };
+        let output = quote! {
+            impl ::admin_sep::HasAdmin for Args {
+                fn admin(&self) -> &::soroban_sdk::Address {
+                    &self.owner
+                }
+            }
+        };
+        equal_tokens(&output, &derive(&input));
+    }
+
+    #[test]
+    fn tuple() {
+        let input: DeriveInput = syn::parse_quote! {
+            pub struct Args(pub u32, #[admin] pub Address);
+        };
+        let output = quote! {
+            impl ::admin_sep::HasAdmin for Args {
+                fn admin(&self) -> &::soroban_sdk::Address {
+                    &self.1
+                }
+            }
+        };
+        equal_tokens(&output, &derive(&input));
+    }
+
+    #[test]
+    fn unmarked() {
+        let input: DeriveInput = syn::parse_quote! {
+            pub struct Args(pub Address);
+        };
+        assert!(derive(&input).to_string().contains("compile_error"));
+    }
+
+    #[test]
+    fn marked_twice() {
+        let input: DeriveInput = syn::parse_quote! {
+            pub struct Args(#[admin] pub Address, #[admin] pub Address);
+        };
+        assert!(derive(&input)
+            .to_string()
+            .contains("Only one field can be marked with #[admin]"));
+    }
+}
@@ -6,30 +6,32 @@ use proc_macro::TokenStream;
 mod args;
 mod contracttrait;
 mod error;
+mod has_admin;
+mod storage_key;
 mod util;
 
 /// # Creates a Contract Trait
-/// 
+///
 /// A contract trait is defines an interface of a contract and declaritive macro with the same name.
-/// 
+///
 /// When writing a soroban contract, you must expose a methods in an implementation with `#[contractimpl]`.
 /// This works for implementations of traits, but the implementation must define all methods of the trait.
-/// 
+///
 /// For example, consider the following trait and a default implementation:
-/// 
+///
 /// ```ignore
 /// trait Administratable {
 ///    fn admin(env: &Env) -> Address;
 ///    fn set_admin(env: &Env, new_admin: &Address);
 /// }
-/// 
+///
 /// struct Admin;
 /// impl Administratable for Admin {
 ///    fn admin(env: &Env) -> Address {
 ///  //...
-/// 
+///
 /// }
-/// 
+///
 /// #[contract]
 /// pub struct Contract;
 /// #[contractimpl]
@@ -40,12 +42,12 @@ mod util;
 ///     fn set_admin(env: &Env, new_admin: &Address) {
 ///       Admin::set_admin(env, new_admin);
 ///     }
-/// } 
+/// }
 /// ```
 ///
 /// Now this works, but it is not very convenient and is very verbose.
 /// One way to make this more convenient is to use an associated type in the trait:
-/// 
+///
 /// ```ignore
 /// trait Administratable {
 ///   type Impl: Administratable;
@@ -57,8 +59,45 @@ mod util;
 ///   }
 /// }
 /// ```
-/// 
-/// 
+///
+///
+/// # Fallible methods
+///
+/// Methods, including `#[internal]` ones, can return `Result<T, E>` where `E` is a
+/// `#[contracterror]`. The result is passed through `Self::Impl` and the generated
+/// `#[contractimpl]` wrapper unchanged, so extensions can use `?` on internal helpers.
+/// Refer to types from the trait's own crate with a `crate::` path, e.g.
+/// `Result<(), crate::AdminError>`; the wrapper rewrites it to `$crate::` so it resolves
+/// in the contract's crate.
+///
+/// # Authorization arguments
+///
+/// Each method returning a `Result`, other than `#[internal]` ones and `__`-prefixed hooks,
+/// gets a hidden `<method>_auth_args` taking the method's arguments by reference, other than
+/// `env` and a `caller`, `operator` or `approver`. It returns them as a `Vec<Val>` for
+/// `require_auth_for_args`, so extensions bind the auth to the method's arguments, e.g.
+/// `Self::upgrade_auth_args(env, &wasm_hash)`.
+///
+/// # Storage keys
+///
+/// The trait gets a `STORAGE_KEYS` constant of `(namespace, variants)` pairs, read from its
+/// `Impl` so it follows extensions to the default implementation. An implementation with
+/// `type Impl = Self` declares the key enums it stores under, e.g.
+/// `&[(K::NAMESPACE, AdministratableKey::VARIANTS)]`, with `VARIANTS` from `#[derive(StorageKey)]`.
+/// `derive_contract` uses it to check the keys don't collide.
+///
+/// # Initialization
+///
+/// `init_required = true` marks an extension trait whose `init` must be run by the
+/// constructor, by wrapping `Constructable` in the trait's extension. `derive_contract`
+/// fails to compile if the trait is composed with a `Constructable` that isn't.
+///
+/// # Arguments type
+///
+/// A contract trait can have a single type parameter, the type of its methods' arguments,
+/// e.g. `trait Constructable<T = Address>`. The contract picks it with `args = MyArgs` in
+/// `derive_contract`, or gets the parameter's default.
+///
 /// # Panics
 ///
 /// This macro will panic if:
@@ -75,12 +114,23 @@ pub fn contracttrait(attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 /// Derives a contract trait for the given Contract struct.
-/// 
+///
 /// ```ignore
 /// #[contract]
 /// #[derive_contract(Administratable, Upgradable(ext = AdministratableExt))]
 /// pub struct Contract;
 /// ```
+///
+/// `default` replaces a trait's default implementation and can be any type, e.g.
+/// `Administratable(default = Admin<Persistent>)`. `args` is the arguments type of a
+/// generic trait, e.g. `Constructable(ext = AdministratableExt, args = MyArgs)`.
+///
+/// Compilation fails if two of the traits store the same key enum variant in the same
+/// namespace, or a trait stores one of the contract's own keys, passed as `(Namespace, KeyEnum)`
+/// pairs, e.g. `storage_keys = [(MyNamespace, MyKey)]`.
+///
+/// With `Constructable`, compilation also fails if it isn't wrapped in the extension of a
+/// composed trait with `init_required`, e.g. `Constructable(ext = AdministratableExt)`.
 #[proc_macro_attribute]
 pub fn derive_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     let (parsed_args, parsed) = match args::parse(attr, item) {
@@ -89,3 +139,37 @@ pub fn derive_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     };
     contracttrait::derive_contract(&parsed_args, &parsed).into()
 }
+
+/// Implements `admin_sep::HasAdmin` for a struct of constructor arguments, returning the
+/// field marked with `#[admin]`.
+///
+/// ```ignore
+/// #[contracttype]
+/// #[derive(HasAdmin)]
+/// pub struct CustomArgs(#[admin] pub Address, pub u32);
+/// ```
+#[proc_macro_derive(HasAdmin, attributes(admin))]
+pub fn derive_has_admin(item: TokenStream) -> TokenStream {
+    match syn::parse(item) {
+        Ok(input) => has_admin::derive(&input).into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Implements `admin_sep::StorageKey` for a storage key enum, listing the names of its
+/// variants, which are all a `#[contracttype]` enum encodes of a key's type.
+///
+/// ```ignore
+/// #[contracttype]
+/// #[derive(StorageKey)]
+/// pub enum MyKey {
+///     Count,
+/// }
+/// ```
+#[proc_macro_derive(StorageKey)]
+pub fn derive_storage_key(item: TokenStream) -> TokenStream {
+    match syn::parse(item) {
+        Ok(input) => storage_key::derive(&input).into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,60 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput};
+
+use crate::error::Error;
+
+pub fn derive(input: &DeriveInput) -> TokenStream {
+    derive_inner(input).unwrap_or_else(Into::into)
+}
+
+fn derive_inner(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let Data::Enum(data) = &input.data else {
+        return Err(
+            syn::Error::new_spanned(input, "StorageKey can only be derived for enums").into(),
+        );
+    };
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| variant.ident.to_string());
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::admin_sep::StorageKey for #name #ty_generics #where_clause {
+            const VARIANTS: &'static [&'static str] = &[#(#variants),*];
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::*;
+
+    #[test]
+    fn variants() {
+        let input: DeriveInput = syn::parse_quote! {
+            pub enum AccessControlKey {
+                Role(Symbol, Address),
+                RoleAdmin(Symbol),
+            }
+        };
+        let output = quote! {
+            impl ::admin_sep::StorageKey for AccessControlKey {
+                const VARIANTS: &'static [&'static str] = &["Role", "RoleAdmin"];
+            }
+        };
+        equal_tokens(&output, &derive(&input));
+    }
+
+    #[test]
+    fn not_an_enum() {
+        let input: DeriveInput = syn::parse_quote! {
+            pub struct Key;
+        };
+        assert!(derive(&input)
+            .to_string()
+            .contains("StorageKey can only be derived for enums"));
+    }
+}